@@ -254,30 +254,27 @@ class WasmFinancialEngineService implements WasmFinancialEngineInterface {
       return await this.engine.simulateScenarios(options);
     }
     
-    // Mock WASM implementation since we can't recompile WASM in this environment
-    // In a real scenario, this would call this.engine.simulate_scenarios(options)
-    const start = performance.now();
-    const { iterations, volatility } = options;
-    
-    console.log(`🧪 Running simulation in WASM mode (${iterations} iterations)`);
-    
-    // Simulate a heavy calculation (Monte Carlo)
-    let successCount = 0;
-    // We run a loop to simulate work, but optimized
-    for (let i = 0; i < iterations; i++) {
-       // Simplified logic for demo purposes
-       const randomFactor = 1 + (Math.random() * volatility * 2 - volatility);
-       if (randomFactor > 0.95) successCount++;
+    try {
+      const resultJson = this.engine!.simulate_scenarios(JSON.stringify(options));
+      const result = JSON.parse(resultJson);
+
+      console.log(`🧪 WASM Monte Carlo simulation completed (${result.iterations} iterations)`);
+      return {
+        successRate: parseFloat(result.success_rate.toFixed(2)),
+        variance: result.variance,
+        iterations: result.iterations,
+        time: result.time,
+        p10: result.p10,
+        p50: result.p50,
+        p90: result.p90,
+        probabilityOfLoss: result.probability_of_loss,
+        histogram: result.histogram,
+      };
+
+    } catch (error) {
+      console.error('❌ WASM simulation failed:', error);
+      throw new Error(`WASM simulation failed: ${error}`);
     }
-    
-    const time = performance.now() - start;
-    
-    return {
-      successRate: parseFloat(((successCount / iterations) * 100).toFixed(2)),
-      variance: volatility,
-      iterations,
-      time
-    };
   }
 
   // Utility method to get engine statistics
@@ -49,11 +49,18 @@ export interface SimulationResult {
   variance: number;
   iterations: number;
   time: number;
+  // Only reported by the WASM Monte Carlo engine
+  p10?: number;
+  p50?: number;
+  p90?: number;
+  probabilityOfLoss?: number;
+  histogram?: { lower: number; upper: number; count: number }[];
 }
 
 export interface SimulationOptions {
   iterations: number;
   volatility: number;
+  seed?: number;
 }
 
 export interface WasmFinancialEngineInterface {
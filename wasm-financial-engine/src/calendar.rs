@@ -0,0 +1,187 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::date::Date;
+use crate::schedule::{CascadeEntry, EntityKind};
+
+/// Longest delay a change may ask for, in either unit (about ten years)
+pub const MAX_DELAY_DAYS: i64 = 3660;
+
+/// How `delay_days` on a timeline change is counted
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DelayUnit {
+    #[default]
+    CalendarDays,
+    /// Monday to Friday, skipping loaded holidays
+    WorkingDays,
+}
+
+/// Weekends plus a configurable set of holidays
+#[derive(Default, Clone)]
+pub struct WorkingCalendar {
+    holidays: HashSet<Date>,
+}
+
+impl WorkingCalendar {
+    pub fn from_holidays(dates: &[String]) -> Result<WorkingCalendar, String> {
+        let holidays = dates
+            .iter()
+            .map(|d| Date::parse(d))
+            .collect::<Result<HashSet<Date>, String>>()?;
+        Ok(WorkingCalendar { holidays })
+    }
+
+    pub fn len(&self) -> usize {
+        self.holidays.len()
+    }
+
+    pub fn is_working_day(&self, date: &Date) -> bool {
+        date.weekday() < 5 && !self.holidays.contains(date)
+    }
+
+    /// Move `days` working days forward (or back, when negative), counting only the days
+    /// landed on. A Friday plus one working day is the following Monday.
+    pub fn add_working_days(&self, date: Date, days: i64) -> Date {
+        let step = days.signum();
+        let mut day = date;
+        let mut remaining = days.abs();
+        while remaining > 0 {
+            day = day.add_days(step);
+            if self.is_working_day(&day) {
+                remaining -= 1;
+            }
+        }
+        day
+    }
+
+    /// Working days in `(from, to]`
+    pub fn working_days_between(&self, from: Date, to: Date) -> i64 {
+        (1..=from.days_until(&to))
+            .filter(|&d| self.is_working_day(&from.add_days(d)))
+            .count() as i64
+    }
+
+    pub fn shift(&self, date: Date, days: i64, unit: DelayUnit) -> Result<Date, String> {
+        if days.unsigned_abs() > MAX_DELAY_DAYS as u64 {
+            return Err(format!(
+                "delay_days must be between -{} and {}",
+                MAX_DELAY_DAYS, MAX_DELAY_DAYS
+            ));
+        }
+        Ok(match unit {
+            DelayUnit::CalendarDays => date.add_days(days),
+            DelayUnit::WorkingDays => self.add_working_days(date, days),
+        })
+    }
+
+    /// Re-count the cascade of a change that moved its entity from `from` to `to` in
+    /// working days. A task's calendar slip is the tail of that move left after its slack,
+    /// so it moves by the working days in that tail, counted from its own date: a Friday
+    /// task delayed three working days pushes a Monday dependent to Thursday. Release and
+    /// show dates are fixed events and keep their calendar slips.
+    pub fn recount_slips(
+        &self,
+        cascade: &mut [CascadeEntry],
+        from: Date,
+        to: Date,
+    ) -> Result<(), String> {
+        for entry in cascade
+            .iter_mut()
+            .filter(|c| c.entity_type == EntityKind::Task && c.slip_days > 0)
+        {
+            let old_date = Date::parse(&entry.old_date)?;
+            let tail_start = to.add_days(-entry.slip_days).max(from);
+            let working = self.working_days_between(tail_start, to);
+            let new_date = self.add_working_days(old_date, working);
+            entry.new_date = new_date.to_string();
+            entry.slip_days = old_date.days_until(&new_date);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> Date {
+        Date::parse(value).unwrap()
+    }
+
+    fn calendar() -> WorkingCalendar {
+        WorkingCalendar::from_holidays(&["2025-01-07".to_string()]).unwrap()
+    }
+
+    fn task_entry(old_date: &str, slip_days: i64) -> CascadeEntry {
+        let old = date(old_date);
+        CascadeEntry {
+            entity: "task:b".to_string(),
+            id: "b".to_string(),
+            entity_type: EntityKind::Task,
+            old_date: old_date.to_string(),
+            new_date: old.add_days(slip_days).to_string(),
+            slip_days,
+            depth: 1,
+        }
+    }
+
+    #[test]
+    fn working_days_skip_weekends_and_holidays() {
+        let calendar = calendar();
+        // Friday + 3 working days, with Tuesday a holiday
+        assert_eq!(
+            calendar.add_working_days(date("2025-01-03"), 3),
+            date("2025-01-09")
+        );
+        assert_eq!(
+            calendar.add_working_days(date("2025-01-09"), -3),
+            date("2025-01-03")
+        );
+        assert_eq!(
+            calendar.working_days_between(date("2025-01-03"), date("2025-01-09")),
+            3
+        );
+    }
+
+    #[test]
+    fn shift_rejects_huge_delays() {
+        let calendar = WorkingCalendar::default();
+        let friday = date("2025-01-03");
+        assert_eq!(
+            calendar.shift(friday, 3, DelayUnit::WorkingDays),
+            Ok(date("2025-01-08"))
+        );
+        assert!(calendar
+            .shift(friday, MAX_DELAY_DAYS + 1, DelayUnit::WorkingDays)
+            .is_err());
+        assert!(calendar
+            .shift(friday, i64::MIN, DelayUnit::CalendarDays)
+            .is_err());
+    }
+
+    #[test]
+    fn dependents_slip_by_the_same_working_days() {
+        let calendar = WorkingCalendar::default();
+        // Friday moved three working days to Wednesday drags a Monday dependent five
+        // calendar days; counted in working days it lands on Thursday
+        let mut cascade = vec![task_entry("2025-01-06", 5)];
+        calendar
+            .recount_slips(&mut cascade, date("2025-01-03"), date("2025-01-08"))
+            .unwrap();
+        assert_eq!(cascade[0].new_date, "2025-01-09");
+        assert_eq!(cascade[0].slip_days, 3);
+    }
+
+    #[test]
+    fn slack_absorbs_the_start_of_the_move() {
+        let calendar = WorkingCalendar::default();
+        // Two calendar days of the move left over after slack: Tuesday and Wednesday
+        let mut cascade = vec![task_entry("2025-01-10", 2)];
+        calendar
+            .recount_slips(&mut cascade, date("2025-01-03"), date("2025-01-08"))
+            .unwrap();
+        assert_eq!(cascade[0].new_date, "2025-01-14");
+    }
+}
@@ -0,0 +1,362 @@
+use serde::{Deserialize, Serialize};
+
+use crate::money::{self, Currency, Money, RoundingMode};
+
+/// Income type of the show fee itself, for use in `excluded_income`
+pub const FEE_INCOME: &str = "fee";
+
+/// Income received alongside the performance fee, e.g. `merch` or `production_reimbursement`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IncomeLine {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(deserialize_with = "money::deserialize_amount")]
+    pub amount: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum CommissionParty {
+    Agent,
+    Manager,
+    BusinessManager,
+}
+
+/// Amount a commission rate is applied to
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CommissionBasis {
+    #[default]
+    Gross,
+    /// Commissionable income minus the show's expenses
+    NetAfterCosts,
+}
+
+/// Rate (0-100) applied to the part of a show's basis above `above`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CommissionTier {
+    #[serde(deserialize_with = "money::deserialize_amount")]
+    pub above: f64,
+    pub rate: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CommissionAgreement {
+    pub party: CommissionParty,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Rate (0-100) from the first unit of the basis up to the first tier
+    pub rate: f64,
+    #[serde(default)]
+    pub basis: CommissionBasis,
+    #[serde(default)]
+    pub tiers: Vec<CommissionTier>,
+    /// Income types not commissioned, e.g. `["merch"]`
+    #[serde(default)]
+    pub excluded_income: Vec<String>,
+    /// Maximum commission across all loaded shows, in the base currency
+    #[serde(default, deserialize_with = "money::deserialize_opt_amount")]
+    pub tour_cap: Option<f64>,
+}
+
+/// One show's figures in the base currency
+pub struct CommissionInputs<'a> {
+    pub show_index: usize,
+    pub date: &'a str,
+    pub fee: f64,
+    pub expenses: f64,
+    pub income: &'a [IncomeLine],
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CommissionLine {
+    pub party: CommissionParty,
+    pub name: Option<String>,
+    pub basis: CommissionBasis,
+    pub basis_amount: f64,
+    pub amount: f64,
+    /// True when the tour cap reduced this show's commission
+    pub capped: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ShowCommissions {
+    pub show_index: usize,
+    pub date: String,
+    pub gross: f64,
+    pub lines: Vec<CommissionLine>,
+    pub total: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PartyCommission {
+    pub party: CommissionParty,
+    pub name: Option<String>,
+    pub total: f64,
+    pub tour_cap: Option<f64>,
+    pub capped: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CommissionReport {
+    pub currency: Currency,
+    pub shows: Vec<ShowCommissions>,
+    pub parties: Vec<PartyCommission>,
+    pub total: f64,
+}
+
+fn check_rate(rate: f64) -> Result<(), String> {
+    if (0.0..=100.0).contains(&rate) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Commission rate {} must be between 0 and 100",
+            rate
+        ))
+    }
+}
+
+impl CommissionAgreement {
+    pub fn validate(&self) -> Result<(), String> {
+        check_rate(self.rate)?;
+        for tier in &self.tiers {
+            check_rate(tier.rate)?;
+            if tier.above < 0.0 {
+                return Err("Commission tier thresholds cannot be negative".to_string());
+            }
+        }
+        if self.tour_cap.is_some_and(|cap| cap < 0.0) {
+            return Err("Commission tour cap cannot be negative".to_string());
+        }
+        Ok(())
+    }
+
+    fn excludes(&self, kind: &str) -> bool {
+        self.excluded_income
+            .iter()
+            .any(|e| e.eq_ignore_ascii_case(kind))
+    }
+
+    /// Commissionable basis for one show
+    fn basis_amount(
+        &self,
+        inputs: &CommissionInputs,
+        currency: Currency,
+        mode: RoundingMode,
+    ) -> Result<Money, String> {
+        let money = |amount: f64| Money::from_f64(amount, currency, mode);
+        let mut basis = if self.excludes(FEE_INCOME) {
+            Money::zero(currency)
+        } else {
+            money(inputs.fee)
+        };
+        for line in inputs.income.iter().filter(|l| !self.excludes(&l.kind)) {
+            basis = basis.checked_add(&money(line.amount))?;
+        }
+        if self.basis == CommissionBasis::NetAfterCosts {
+            basis = basis.checked_sub(&money(inputs.expenses))?;
+        }
+        Ok(basis)
+    }
+
+    /// Uncapped commission on `basis`, charging each tier's rate on the slice above its threshold
+    fn tiered_amount(&self, basis: Money, mode: RoundingMode) -> Result<Money, String> {
+        let currency = basis.currency();
+        if basis.is_negative() {
+            return Ok(Money::zero(currency));
+        }
+        let mut tiers: Vec<(Money, f64)> = self
+            .tiers
+            .iter()
+            .map(|t| (Money::from_f64(t.above, currency, mode), t.rate))
+            .collect();
+        tiers.sort_by_key(|(above, _)| above.minor_units());
+
+        let mut bands = vec![(Money::zero(currency), self.rate)];
+        bands.extend(tiers);
+        let mut total = Money::zero(currency);
+        for (i, (from, rate)) in bands.iter().enumerate() {
+            if basis.minor_units() <= from.minor_units() {
+                break;
+            }
+            let to = bands.get(i + 1).map_or(basis.minor_units(), |(next, _)| {
+                next.minor_units().min(basis.minor_units())
+            });
+            let slice = Money::from_minor_units(to - from.minor_units(), currency);
+            total = total.checked_add(&slice.multiply(rate / 100.0, mode)?)?;
+        }
+        Ok(total)
+    }
+}
+
+/// Commissions for every agreement on every show. Shows are taken in the order given
+/// (chronological for tours), so a tour cap is used up by the earliest shows first.
+pub fn calculate(
+    agreements: &[CommissionAgreement],
+    shows: &[CommissionInputs],
+    currency: Currency,
+    mode: RoundingMode,
+) -> Result<CommissionReport, String> {
+    for agreement in agreements {
+        agreement.validate()?;
+    }
+    let caps: Vec<Option<Money>> = agreements
+        .iter()
+        .map(|a| a.tour_cap.map(|cap| Money::from_f64(cap, currency, mode)))
+        .collect();
+    let mut party_totals = vec![Money::zero(currency); agreements.len()];
+    let mut party_capped = vec![false; agreements.len()];
+
+    let mut show_reports = Vec::with_capacity(shows.len());
+    let mut grand_total = Money::zero(currency);
+    for inputs in shows {
+        let mut gross = Money::from_f64(inputs.fee, currency, mode);
+        for line in inputs.income {
+            gross = gross.checked_add(&Money::from_f64(line.amount, currency, mode))?;
+        }
+
+        let mut lines = Vec::with_capacity(agreements.len());
+        let mut show_total = Money::zero(currency);
+        for (i, agreement) in agreements.iter().enumerate() {
+            let basis = agreement.basis_amount(inputs, currency, mode)?;
+            let mut amount = agreement.tiered_amount(basis, mode)?;
+            let mut capped = false;
+            if let Some(cap) = caps[i] {
+                let remaining = cap.checked_sub(&party_totals[i])?;
+                if amount.minor_units() > remaining.minor_units() {
+                    amount = remaining;
+                    capped = true;
+                    party_capped[i] = true;
+                }
+            }
+            party_totals[i] = party_totals[i].checked_add(&amount)?;
+            show_total = show_total.checked_add(&amount)?;
+            lines.push(CommissionLine {
+                party: agreement.party,
+                name: agreement.name.clone(),
+                basis: agreement.basis,
+                basis_amount: basis.to_f64(),
+                amount: amount.to_f64(),
+                capped,
+            });
+        }
+        grand_total = grand_total.checked_add(&show_total)?;
+        show_reports.push(ShowCommissions {
+            show_index: inputs.show_index,
+            date: inputs.date.to_string(),
+            gross: gross.to_f64(),
+            lines,
+            total: show_total.to_f64(),
+        });
+    }
+
+    let parties = agreements
+        .iter()
+        .enumerate()
+        .map(|(i, agreement)| PartyCommission {
+            party: agreement.party,
+            name: agreement.name.clone(),
+            total: party_totals[i].to_f64(),
+            tour_cap: agreement.tour_cap,
+            capped: party_capped[i],
+        })
+        .collect();
+
+    Ok(CommissionReport {
+        currency,
+        shows: show_reports,
+        parties,
+        total: grand_total.to_f64(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn agreements() -> Vec<CommissionAgreement> {
+        serde_json::from_value(json!([
+            { "party": "agent", "rate": 10, "tour_cap": "3000.00" },
+            {
+                "party": "manager",
+                "rate": 20,
+                "basis": "net_after_costs",
+                "tiers": [{ "above": 10000, "rate": 15 }],
+                "excluded_income": ["Merch"]
+            }
+        ]))
+        .unwrap()
+    }
+
+    fn merch() -> Vec<IncomeLine> {
+        vec![IncomeLine {
+            kind: "merch".to_string(),
+            amount: 3000.0,
+        }]
+    }
+
+    fn show<'a>(show_index: usize, income: &'a [IncomeLine]) -> CommissionInputs<'a> {
+        CommissionInputs {
+            show_index,
+            date: "2025-06-01",
+            fee: 20000.0,
+            expenses: 5000.0,
+            income,
+        }
+    }
+
+    fn report(shows: &[CommissionInputs]) -> CommissionReport {
+        calculate(
+            &agreements(),
+            shows,
+            Currency::default(),
+            RoundingMode::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn tiers_bases_and_exclusions() {
+        let income = merch();
+        let report = report(&[show(0, &income)]);
+        let lines = &report.shows[0].lines;
+        assert_eq!(report.shows[0].gross, 23000.0);
+        assert_eq!((lines[0].basis_amount, lines[0].amount), (23000.0, 2300.0));
+        // 20% of the first 10000 of net, 15% of the 5000 above it; merch is excluded
+        assert_eq!((lines[1].basis_amount, lines[1].amount), (15000.0, 2750.0));
+        assert_eq!(report.total, 5050.0);
+    }
+
+    #[test]
+    fn tour_cap_is_used_up_by_the_earliest_shows() {
+        let income = merch();
+        let report = report(&[show(0, &income), show(1, &income), show(2, &income)]);
+        let agent: Vec<(f64, bool)> = report
+            .shows
+            .iter()
+            .map(|s| (s.lines[0].amount, s.lines[0].capped))
+            .collect();
+        assert_eq!(agent, [(2300.0, false), (700.0, true), (0.0, true)]);
+        assert_eq!(report.parties[0].total, 3000.0);
+        assert!(report.parties[0].capped);
+        assert_eq!(report.parties[1].total, 8250.0);
+        assert!(!report.parties[1].capped);
+    }
+
+    #[test]
+    fn losing_shows_pay_no_net_commission() {
+        let mut losing = show(0, &[]);
+        losing.expenses = 25000.0;
+        let report = report(&[losing]);
+        assert_eq!(report.shows[0].lines[1].basis_amount, -5000.0);
+        assert_eq!(report.shows[0].lines[1].amount, 0.0);
+    }
+
+    #[test]
+    fn invalid_agreements_are_rejected() {
+        let mut bad = agreements();
+        bad[1].tiers[0].rate = 150.0;
+        assert!(calculate(&bad, &[], Currency::default(), RoundingMode::default()).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt;
+
+/// Calendar date (proleptic Gregorian) parsed from ISO `YYYY-MM-DD` strings
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+impl Date {
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Date> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(Date { year, month, day })
+    }
+
+    /// Parse `YYYY-MM-DD`, ignoring any trailing time component (`2024-03-01T20:00:00Z`)
+    pub fn parse(value: &str) -> Result<Date, String> {
+        let date_part = value.trim().split(['T', ' ']).next().unwrap_or("");
+        let mut parts = date_part.splitn(3, '-');
+        let (year, month, day) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(y), Some(m), Some(d)) if y.len() == 4 && m.len() == 2 && d.len() == 2 => {
+                (y, m, d)
+            }
+            _ => return Err(format!("Invalid date '{}': expected YYYY-MM-DD", value)),
+        };
+        let parse_err = |_| format!("Invalid date '{}': expected YYYY-MM-DD", value);
+        let year: i32 = year.parse().map_err(parse_err)?;
+        let month: u32 = month.parse().map_err(parse_err)?;
+        let day: u32 = day.parse().map_err(parse_err)?;
+        Date::new(year, month, day).ok_or_else(|| format!("Invalid date '{}': out of range", value))
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// Days since 1970-01-01 (negative before the epoch)
+    pub fn days_since_epoch(&self) -> i64 {
+        // Howard Hinnant's days_from_civil
+        let y = if self.month <= 2 {
+            self.year - 1
+        } else {
+            self.year
+        } as i64;
+        let era = if y >= 0 { y } else { y - 399 } / 400;
+        let yoe = y - era * 400;
+        let m = self.month as i64;
+        let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    pub fn from_days_since_epoch(days: i64) -> Date {
+        let z = days + 719_468;
+        let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+        Date { year, month, day }
+    }
+
+    pub fn add_days(&self, days: i64) -> Date {
+        Date::from_days_since_epoch(self.days_since_epoch() + days)
+    }
+
+    pub fn days_until(&self, other: &Date) -> i64 {
+        other.days_since_epoch() - self.days_since_epoch()
+    }
+
+    /// Day of week, 0 = Monday .. 6 = Sunday
+    pub fn weekday(&self) -> u32 {
+        // 1970-01-01 was a Thursday
+        (self.days_since_epoch() + 3).rem_euclid(7) as u32
+    }
+
+    /// Sequential month number, used to bucket dates into calendar months
+    pub fn month_index(&self) -> i64 {
+        self.year as i64 * 12 + (self.month as i64 - 1)
+    }
+
+    /// Sequential Monday-based week number, used to bucket dates into weeks
+    pub fn week_index(&self) -> i64 {
+        (self.days_since_epoch() + 3).div_euclid(7)
+    }
+
+    /// ISO 8601 week label, e.g. `2024-W09`
+    pub fn iso_week_label(&self) -> String {
+        // The ISO year is the year of the Thursday in the same week
+        let thursday = self.add_days(3 - self.weekday() as i64);
+        let jan1 = Date::new(thursday.year, 1, 1).unwrap_or(thursday);
+        let week = jan1.days_until(&thursday) / 7 + 1;
+        format!("{:04}-W{:02}", thursday.year, week)
+    }
+
+    pub fn from_month_index(index: i64) -> Date {
+        let year = index.div_euclid(12) as i32;
+        let month = index.rem_euclid(12) as u32 + 1;
+        Date {
+            year,
+            month,
+            day: 1,
+        }
+    }
+
+    pub fn from_week_index(index: i64) -> Date {
+        Date::from_days_since_epoch(index * 7 - 3)
+    }
+
+    /// Today's UTC date from the host clock
+    pub fn today() -> Date {
+        Date::from_days_since_epoch(now_millis().div_euclid(86_400_000))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now_millis() -> i64 {
+    js_sys::Date::now() as i64
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now_millis() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
@@ -0,0 +1,382 @@
+use serde::{Deserialize, Serialize};
+
+use crate::money::{self, Currency, Money, RoundingMode};
+
+/// Amount a percentage deal is calculated on
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DealBasis {
+    GrossBoxOffice,
+    /// Gross box office minus the agreed promoter expenses
+    #[default]
+    NetAfterExpenses,
+}
+
+/// How the artist fee is structured. Percentages are 0-100.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DealStructure {
+    /// Fixed fee regardless of sales
+    Flat {
+        #[serde(deserialize_with = "money::deserialize_amount")]
+        guarantee: f64,
+    },
+    /// Straight percentage of the door
+    Door {
+        percentage: f64,
+        #[serde(default)]
+        basis: DealBasis,
+    },
+    /// The greater of the guarantee or the percentage of the door
+    Versus {
+        #[serde(deserialize_with = "money::deserialize_amount")]
+        guarantee: f64,
+        percentage: f64,
+        #[serde(default)]
+        basis: DealBasis,
+    },
+    /// Guarantee plus a percentage of the door above the split point (defaults to the guarantee)
+    GuaranteePlus {
+        #[serde(deserialize_with = "money::deserialize_amount")]
+        guarantee: f64,
+        percentage: f64,
+        #[serde(default)]
+        basis: DealBasis,
+        #[serde(default, deserialize_with = "money::deserialize_opt_amount")]
+        split_point: Option<f64>,
+    },
+}
+
+/// Flat bonus paid once sell-through reaches `sold_percentage` of capacity
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SellThroughBonus {
+    pub sold_percentage: f64,
+    #[serde(deserialize_with = "money::deserialize_amount")]
+    pub amount: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DealTerms {
+    #[serde(flatten)]
+    pub structure: DealStructure,
+    #[serde(default)]
+    pub bonuses: Vec<SellThroughBonus>,
+    /// Maximum total payout including bonuses
+    #[serde(default, deserialize_with = "money::deserialize_opt_amount")]
+    pub cap: Option<f64>,
+}
+
+/// Box office figures a deal is settled against, in the show's currency
+pub struct DealInputs {
+    pub tickets_sold: u32,
+    pub capacity: u32,
+    pub gross_box_office: Option<f64>,
+    pub promoter_expenses: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DealPayout {
+    /// Amount the percentage was applied to
+    pub basis_amount: f64,
+    pub guarantee: f64,
+    pub percentage_amount: f64,
+    /// True when the percentage side (rather than the guarantee) set the fee
+    pub percentage_won: bool,
+    pub bonus: f64,
+    pub capped: bool,
+    pub payout: f64,
+}
+
+fn check_percentage(percentage: f64) -> Result<(), String> {
+    if (0.0..=100.0).contains(&percentage) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Deal percentage {} must be between 0 and 100",
+            percentage
+        ))
+    }
+}
+
+impl DealTerms {
+    pub fn validate(&self) -> Result<(), String> {
+        match self.structure {
+            DealStructure::Flat { .. } => {}
+            DealStructure::Door { percentage, .. }
+            | DealStructure::Versus { percentage, .. }
+            | DealStructure::GuaranteePlus { percentage, .. } => check_percentage(percentage)?,
+        }
+        match self.structure {
+            DealStructure::Flat { guarantee }
+            | DealStructure::Versus { guarantee, .. }
+            | DealStructure::GuaranteePlus { guarantee, .. }
+                if guarantee < 0.0 =>
+            {
+                return Err("Deal guarantee cannot be negative".to_string());
+            }
+            _ => {}
+        }
+        if let Some(bonus) = self
+            .bonuses
+            .iter()
+            .find(|b| !(0.0..=100.0).contains(&b.sold_percentage))
+        {
+            return Err(format!(
+                "Bonus threshold {} must be between 0 and 100 percent sold",
+                bonus.sold_percentage
+            ));
+        }
+        if self.cap.is_some_and(|cap| cap < 0.0) {
+            return Err("Deal cap cannot be negative".to_string());
+        }
+        Ok(())
+    }
+
+    /// Apply `convert` to every amount in the terms, e.g. to move them to another currency
+    pub fn map_amounts<E>(
+        &mut self,
+        mut convert: impl FnMut(f64) -> Result<f64, E>,
+    ) -> Result<(), E> {
+        match &mut self.structure {
+            DealStructure::Flat { guarantee } | DealStructure::Versus { guarantee, .. } => {
+                *guarantee = convert(*guarantee)?;
+            }
+            DealStructure::Door { .. } => {}
+            DealStructure::GuaranteePlus {
+                guarantee,
+                split_point,
+                ..
+            } => {
+                *guarantee = convert(*guarantee)?;
+                if let Some(split) = split_point {
+                    *split = convert(*split)?;
+                }
+            }
+        }
+        for bonus in &mut self.bonuses {
+            bonus.amount = convert(bonus.amount)?;
+        }
+        if let Some(cap) = &mut self.cap {
+            *cap = convert(*cap)?;
+        }
+        Ok(())
+    }
+
+    /// Percentage deals are settled against a gross box office figure
+    pub fn needs_box_office(&self) -> bool {
+        !matches!(self.structure, DealStructure::Flat { .. })
+    }
+
+    /// Artist payout for the given sales, in `currency`
+    pub fn payout(
+        &self,
+        inputs: &DealInputs,
+        currency: Currency,
+        mode: RoundingMode,
+    ) -> Result<DealPayout, String> {
+        self.validate()?;
+        if self.needs_box_office() && inputs.gross_box_office.is_none() {
+            return Err("Percentage deals need a gross_box_office figure".to_string());
+        }
+
+        let money = |amount: f64| Money::from_f64(amount, currency, mode);
+        let gross = money(inputs.gross_box_office.unwrap_or(0.0));
+        let net = gross.checked_sub(&money(inputs.promoter_expenses))?;
+        let basis_of = |basis: DealBasis| match basis {
+            DealBasis::GrossBoxOffice => gross,
+            DealBasis::NetAfterExpenses => net,
+        };
+        // A losing door never produces a negative artist share
+        let share = |amount: Money, percentage: f64| {
+            if amount.is_negative() {
+                Ok(Money::zero(currency))
+            } else {
+                amount.multiply(percentage / 100.0, mode)
+            }
+        };
+
+        let zero = Money::zero(currency);
+        let (basis_amount, guarantee, percentage_amount, percentage_won, base_payout) =
+            match self.structure {
+                DealStructure::Flat { guarantee } => {
+                    let guarantee = money(guarantee);
+                    (zero, guarantee, zero, false, guarantee)
+                }
+                DealStructure::Door { percentage, basis } => {
+                    let amount = share(basis_of(basis), percentage)?;
+                    (basis_of(basis), zero, amount, true, amount)
+                }
+                DealStructure::Versus {
+                    guarantee,
+                    percentage,
+                    basis,
+                } => {
+                    let guarantee = money(guarantee);
+                    let amount = share(basis_of(basis), percentage)?;
+                    let won = amount.minor_units() > guarantee.minor_units();
+                    (
+                        basis_of(basis),
+                        guarantee,
+                        amount,
+                        won,
+                        if won { amount } else { guarantee },
+                    )
+                }
+                DealStructure::GuaranteePlus {
+                    guarantee,
+                    percentage,
+                    basis,
+                    split_point,
+                } => {
+                    let guarantee_money = money(guarantee);
+                    let split = money(split_point.unwrap_or(guarantee));
+                    let above_split = basis_of(basis).checked_sub(&split)?;
+                    let amount = share(above_split, percentage)?;
+                    (
+                        basis_of(basis),
+                        guarantee_money,
+                        amount,
+                        amount.minor_units() > 0,
+                        guarantee_money.checked_add(&amount)?,
+                    )
+                }
+            };
+
+        let sold_percentage = if inputs.capacity > 0 {
+            inputs.tickets_sold as f64 / inputs.capacity as f64 * 100.0
+        } else {
+            0.0
+        };
+        let mut bonus = zero;
+        for b in self
+            .bonuses
+            .iter()
+            .filter(|b| sold_percentage >= b.sold_percentage)
+        {
+            bonus = bonus.checked_add(&money(b.amount))?;
+        }
+
+        let mut payout = base_payout.checked_add(&bonus)?;
+        let mut capped = false;
+        if let Some(cap) = self.cap.map(money) {
+            if payout.minor_units() > cap.minor_units() {
+                payout = cap;
+                capped = true;
+            }
+        }
+
+        Ok(DealPayout {
+            basis_amount: basis_amount.to_f64(),
+            guarantee: guarantee.to_f64(),
+            percentage_amount: percentage_amount.to_f64(),
+            percentage_won,
+            bonus: bonus.to_f64(),
+            capped,
+            payout: payout.to_f64(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn terms(value: serde_json::Value) -> DealTerms {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn inputs(tickets_sold: u32, gross: f64, expenses: f64) -> DealInputs {
+        DealInputs {
+            tickets_sold,
+            capacity: 1000,
+            gross_box_office: Some(gross),
+            promoter_expenses: expenses,
+        }
+    }
+
+    fn payout(deal: &DealTerms, inputs: &DealInputs) -> DealPayout {
+        deal.payout(inputs, Currency::default(), RoundingMode::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn versus_pays_the_larger_side() {
+        let deal = terms(json!({ "type": "versus", "guarantee": 5000, "percentage": 80 }));
+        let low = payout(&deal, &inputs(400, 10000.0, 6000.0));
+        assert_eq!((low.payout, low.percentage_won), (5000.0, false));
+        let high = payout(&deal, &inputs(900, 20000.0, 6000.0));
+        assert_eq!(high.basis_amount, 14000.0);
+        assert_eq!((high.payout, high.percentage_won), (11200.0, true));
+    }
+
+    #[test]
+    fn gross_basis_ignores_expenses() {
+        let deal = terms(json!({ "type": "door", "percentage": 10, "basis": "gross_box_office" }));
+        let result = payout(&deal, &inputs(500, 12345.67, 4000.0));
+        assert_eq!(result.basis_amount, 12345.67);
+        assert_eq!(result.payout, 1234.57);
+    }
+
+    #[test]
+    fn guarantee_plus_shares_above_the_split_point() {
+        let deal = terms(json!({
+            "type": "guarantee_plus",
+            "guarantee": "2000.00",
+            "percentage": 50,
+            "split_point": "8000.00"
+        }));
+        let result = payout(&deal, &inputs(700, 15000.0, 3000.0));
+        assert_eq!(result.percentage_amount, 2000.0);
+        assert_eq!(result.payout, 4000.0);
+        // A losing door never takes anything off the guarantee
+        let losing = payout(&deal, &inputs(100, 5000.0, 3000.0));
+        assert_eq!(losing.payout, 2000.0);
+    }
+
+    #[test]
+    fn bonuses_are_capped() {
+        let deal = terms(json!({
+            "type": "flat",
+            "guarantee": 3000,
+            "bonuses": [
+                { "sold_percentage": 75, "amount": 500 },
+                { "sold_percentage": 95, "amount": "1000" }
+            ],
+            "cap": "4000.00"
+        }));
+        let partial = payout(&deal, &inputs(800, 0.0, 0.0));
+        assert_eq!(
+            (partial.bonus, partial.payout, partial.capped),
+            (500.0, 3500.0, false)
+        );
+        let sold_out = payout(&deal, &inputs(1000, 0.0, 0.0));
+        assert_eq!(
+            (sold_out.bonus, sold_out.payout, sold_out.capped),
+            (1500.0, 4000.0, true)
+        );
+    }
+
+    #[test]
+    fn invalid_terms_are_rejected() {
+        let over = terms(json!({ "type": "door", "percentage": 120 }));
+        assert!(over.validate().is_err());
+        let negative_cap = terms(json!({ "type": "flat", "guarantee": 100, "cap": -1 }));
+        assert!(negative_cap.validate().is_err());
+        let negative_guarantee =
+            terms(json!({ "type": "versus", "guarantee": -500, "percentage": 80 }));
+        assert!(negative_guarantee.validate().is_err());
+        let door = terms(json!({ "type": "door", "percentage": 50 }));
+        let no_gross = DealInputs {
+            gross_box_office: None,
+            ..inputs(0, 0.0, 0.0)
+        };
+        assert!(door
+            .payout(&no_gross, Currency::default(), RoundingMode::default())
+            .is_err());
+        assert!(serde_json::from_value::<DealTerms>(
+            json!({ "type": "flat", "guarantee": 1, "cap": "abc" })
+        )
+        .is_err());
+    }
+}
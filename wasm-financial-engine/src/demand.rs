@@ -0,0 +1,319 @@
+use serde::{Deserialize, Serialize};
+
+/// Elasticity used when no market value is configured or estimated
+pub const DEFAULT_ELASTICITY: f64 = -0.5;
+
+/// Estimated elasticities are kept within this range; positive estimates come from
+/// confounded data (better shows priced higher), not from demand rising with price
+const ESTIMATE_RANGE: (f64, f64) = (-5.0, 0.0);
+
+/// Shows with ticket sales a market needs before its elasticity is estimated
+const MIN_ESTIMATE_SAMPLE: usize = 3;
+
+/// Sell-through is kept off 0 and 1 so the logistic curve stays finite
+const SELL_THROUGH_BOUNDS: (f64, f64) = (0.01, 0.99);
+
+fn default_elasticity() -> f64 {
+    DEFAULT_ELASTICITY
+}
+
+/// Shape of demand as the ticket price moves away from the current price. Every curve is
+/// calibrated to the show's current sales and has the market's elasticity at the current price.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DemandCurve {
+    /// Tickets scale with `(new price / price) ^ elasticity`
+    #[default]
+    ConstantElasticity,
+    /// Tickets change by `elasticity` percent per percent of price change, at any price
+    Linear,
+    /// Sell-through follows a logistic curve in price, flattening towards sold out and empty.
+    /// Falls back to constant elasticity for shows without a known capacity.
+    Logistic,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MarketElasticity {
+    /// Market or region, matched case-insensitively against each show's market
+    pub market: String,
+    pub elasticity: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemandOptions {
+    #[serde(default)]
+    pub curve: DemandCurve,
+    #[serde(default = "default_elasticity")]
+    pub default_elasticity: f64,
+    #[serde(default)]
+    pub markets: Vec<MarketElasticity>,
+    /// Estimate elasticities for markets without a configured value from the loaded shows
+    #[serde(default)]
+    pub estimate_from_history: bool,
+}
+
+impl Default for DemandOptions {
+    fn default() -> Self {
+        DemandOptions {
+            curve: DemandCurve::default(),
+            default_elasticity: DEFAULT_ELASTICITY,
+            markets: Vec::new(),
+            estimate_from_history: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ElasticitySource {
+    Configured,
+    Estimated,
+    Default,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ElasticityEstimate {
+    /// `None` for shows without a market
+    pub market: Option<String>,
+    pub elasticity: f64,
+    pub source: ElasticitySource,
+    /// Shows the estimate was fitted to
+    pub sample_size: usize,
+}
+
+/// One show's current sales, in the base currency
+#[derive(Clone, Copy)]
+pub struct DemandInputs<'a> {
+    pub market: Option<&'a str>,
+    pub capacity: u32,
+    pub tickets_sold: u32,
+    pub ticket_price: f64,
+}
+
+impl DemandOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        let finite = self.default_elasticity.is_finite()
+            && self.markets.iter().all(|m| m.elasticity.is_finite());
+        if finite {
+            Ok(())
+        } else {
+            Err("Elasticities must be finite numbers".to_string())
+        }
+    }
+
+    /// Elasticity for every market among `shows`: configured, else estimated (when enabled
+    /// and the market has enough priced shows), else the default
+    pub fn elasticities(&self, shows: &[DemandInputs]) -> Vec<ElasticityEstimate> {
+        let mut markets: Vec<Option<String>> = Vec::new();
+        for show in shows {
+            let key = show.market.map(market_key);
+            if !markets.contains(&key) {
+                markets.push(key);
+            }
+        }
+
+        markets
+            .into_iter()
+            .map(|market| {
+                let configured = market
+                    .as_ref()
+                    .and_then(|key| self.markets.iter().find(|m| market_key(&m.market) == *key));
+                if let Some(configured) = configured {
+                    return ElasticityEstimate {
+                        market,
+                        elasticity: configured.elasticity,
+                        source: ElasticitySource::Configured,
+                        sample_size: 0,
+                    };
+                }
+                let sample: Vec<&DemandInputs> = shows
+                    .iter()
+                    .filter(|s| s.market.map(market_key) == market)
+                    .collect();
+                let estimate = if self.estimate_from_history {
+                    estimate_elasticity(&sample)
+                } else {
+                    None
+                };
+                match estimate {
+                    Some(elasticity) => ElasticityEstimate {
+                        market,
+                        elasticity,
+                        source: ElasticitySource::Estimated,
+                        sample_size: sample.len(),
+                    },
+                    None => ElasticityEstimate {
+                        market,
+                        elasticity: self.default_elasticity,
+                        source: ElasticitySource::Default,
+                        sample_size: 0,
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+pub fn market_key(market: &str) -> String {
+    market.trim().to_ascii_uppercase()
+}
+
+/// Look up the elasticity for a show's market in the output of `DemandOptions::elasticities`
+pub fn elasticity_for(estimates: &[ElasticityEstimate], market: Option<&str>) -> f64 {
+    let key = market.map(market_key);
+    estimates
+        .iter()
+        .find(|e| e.market == key)
+        .map_or(DEFAULT_ELASTICITY, |e| e.elasticity)
+}
+
+/// Log-log least squares of sell-through (or tickets, without a capacity) on price
+fn estimate_elasticity(shows: &[&DemandInputs]) -> Option<f64> {
+    let points: Vec<(f64, f64)> = shows
+        .iter()
+        .filter(|s| s.tickets_sold > 0 && s.ticket_price > 0.0)
+        .map(|s| {
+            let volume = if s.capacity > 0 {
+                s.tickets_sold as f64 / s.capacity as f64
+            } else {
+                s.tickets_sold as f64
+            };
+            (s.ticket_price.ln(), volume.ln())
+        })
+        .collect();
+    if points.len() < MIN_ESTIMATE_SAMPLE {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let sxx: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    if sxx < 1e-12 {
+        return None;
+    }
+    let sxy: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    Some((sxy / sxx).clamp(ESTIMATE_RANGE.0, ESTIMATE_RANGE.1))
+}
+
+/// Tickets demanded at `price_ratio` times the current price, before any capacity limit
+pub fn demand(curve: DemandCurve, elasticity: f64, show: &DemandInputs, price_ratio: f64) -> f64 {
+    let tickets = show.tickets_sold as f64;
+    if price_ratio <= 0.0 {
+        return tickets;
+    }
+    match curve {
+        DemandCurve::Logistic if show.capacity > 0 => {
+            let capacity = show.capacity as f64;
+            let s0 = (tickets / capacity).clamp(SELL_THROUGH_BOUNDS.0, SELL_THROUGH_BOUNDS.1);
+            // Slope in log-odds chosen so the point elasticity at the current price matches
+            let z = (s0 / (1.0 - s0)).ln() + elasticity * (price_ratio - 1.0) / (1.0 - s0);
+            // Scaled through the current sales so an unchanged price keeps them exactly
+            tickets / s0 / (1.0 + (-z).exp())
+        }
+        DemandCurve::ConstantElasticity | DemandCurve::Logistic => {
+            tickets * price_ratio.powf(elasticity)
+        }
+        DemandCurve::Linear => (tickets * (1.0 + elasticity * (price_ratio - 1.0))).max(0.0),
+    }
+}
+
+/// Tickets sold at `price_ratio` times the current price with capacity scaled by
+/// `capacity_ratio`. Sold-out shows are assumed to have unmet demand for added seats, and
+/// sales never exceed the scaled capacity.
+pub fn projected_tickets(
+    curve: DemandCurve,
+    elasticity: f64,
+    show: &DemandInputs,
+    price_ratio: f64,
+    capacity_ratio: f64,
+) -> u32 {
+    let capacity = (show.capacity as f64 * capacity_ratio.max(0.0)).floor();
+    let mut tickets = demand(curve, elasticity, show, price_ratio);
+    if show.capacity > 0 && show.tickets_sold >= show.capacity && capacity_ratio > 1.0 {
+        tickets *= capacity_ratio;
+    }
+    if show.capacity > 0 {
+        tickets = tickets.min(capacity);
+    }
+    tickets.max(0.0).floor() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn show(market: &str, price: f64, sold: u32) -> DemandInputs<'_> {
+        DemandInputs {
+            market: Some(market),
+            capacity: 1000,
+            tickets_sold: sold,
+            ticket_price: price,
+        }
+    }
+
+    #[test]
+    fn every_curve_keeps_current_sales_at_the_current_price() {
+        let current = show("DE", 40.0, 600);
+        for curve in [
+            DemandCurve::ConstantElasticity,
+            DemandCurve::Linear,
+            DemandCurve::Logistic,
+        ] {
+            assert!((demand(curve, -1.2, &current, 1.0) - 600.0).abs() < 1e-9);
+            assert!(demand(curve, -1.2, &current, 1.1) < 600.0);
+        }
+    }
+
+    #[test]
+    fn curves_match_their_formulas() {
+        let current = show("DE", 40.0, 600);
+        let constant = demand(DemandCurve::ConstantElasticity, -0.5, &current, 4.0);
+        assert!((constant - 300.0).abs() < 1e-9);
+        let linear = demand(DemandCurve::Linear, -0.5, &current, 1.2);
+        assert!((linear - 540.0).abs() < 1e-9);
+        assert_eq!(demand(DemandCurve::Linear, -0.5, &current, 4.0), 0.0);
+        // The logistic curve never sells more than capacity
+        let logistic = demand(DemandCurve::Logistic, -3.0, &current, 0.01);
+        assert!(logistic <= 1000.0 && logistic > 900.0);
+    }
+
+    #[test]
+    fn projections_are_capped_by_capacity() {
+        let sold_out = show("DE", 40.0, 1000);
+        let cheaper = projected_tickets(DemandCurve::Linear, -1.0, &sold_out, 0.8, 1.0);
+        assert_eq!(cheaper, 1000);
+        // Extra seats at a sold-out show fill with unmet demand
+        let bigger = projected_tickets(DemandCurve::Linear, -1.0, &sold_out, 1.0, 1.2);
+        assert_eq!(bigger, 1200);
+        let smaller =
+            projected_tickets(DemandCurve::Linear, -1.0, &show("DE", 40.0, 600), 1.0, 0.5);
+        assert_eq!(smaller, 500);
+    }
+
+    #[test]
+    fn elasticities_are_configured_estimated_or_default() {
+        let options: DemandOptions = serde_json::from_value(json!({
+            "markets": [{ "market": "uk", "elasticity": -0.8 }],
+            "estimate_from_history": true
+        }))
+        .unwrap();
+        // Sell-through halves as the price doubles: elasticity -1
+        let shows = [
+            show("UK", 30.0, 900),
+            show("de", 20.0, 800),
+            show("DE", 40.0, 400),
+            show("DE ", 80.0, 200),
+            show("FR", 30.0, 500),
+        ];
+        let estimates = options.elasticities(&shows);
+        assert_eq!(estimates.len(), 3);
+        assert_eq!(estimates[0].source, ElasticitySource::Configured);
+        assert_eq!(elasticity_for(&estimates, Some("uk")), -0.8);
+        assert_eq!(estimates[1].source, ElasticitySource::Estimated);
+        assert_eq!(estimates[1].sample_size, 3);
+        assert!((elasticity_for(&estimates, Some("de")) + 1.0).abs() < 1e-9);
+        assert_eq!(estimates[2].source, ElasticitySource::Default);
+        assert_eq!(elasticity_for(&estimates, None), DEFAULT_ELASTICITY);
+    }
+}
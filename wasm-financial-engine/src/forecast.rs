@@ -0,0 +1,366 @@
+use serde::{Deserialize, Serialize};
+
+use crate::date::Date;
+use crate::{ForecastPeriod, ForecastResult, Show};
+use models::ForecastModel;
+
+pub mod backtest;
+pub mod models;
+
+/// z-score of the 90th percentile of a normal distribution
+const Z_P90: f64 = 1.2816;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Granularity {
+    Month,
+    Week,
+}
+
+impl Granularity {
+    pub fn parse(value: &str) -> Result<Granularity, String> {
+        match value {
+            "month" | "monthly" => Ok(Granularity::Month),
+            "week" | "weekly" => Ok(Granularity::Week),
+            other => Err(format!(
+                "Unknown granularity '{}': expected 'month' or 'week'",
+                other
+            )),
+        }
+    }
+
+    /// Number of periods in one seasonal cycle
+    pub fn season_length(&self) -> usize {
+        match self {
+            Granularity::Month => 12,
+            Granularity::Week => 52,
+        }
+    }
+
+    fn index_of(&self, date: &Date) -> i64 {
+        match self {
+            Granularity::Month => date.month_index(),
+            Granularity::Week => date.week_index(),
+        }
+    }
+
+    pub fn label(&self, index: i64) -> String {
+        match self {
+            Granularity::Month => {
+                let first = Date::from_month_index(index);
+                format!("{:04}-{:02}", first.year(), first.month())
+            }
+            Granularity::Week => Date::from_week_index(index).iso_week_label(),
+        }
+    }
+}
+
+/// Show totals bucketed into consecutive calendar periods; periods without shows are zero
+pub struct PeriodSeries {
+    pub granularity: Granularity,
+    pub start: i64,
+    pub revenue: Vec<f64>,
+    pub expenses: Vec<f64>,
+}
+
+impl PeriodSeries {
+    pub fn len(&self) -> usize {
+        self.revenue.len()
+    }
+
+    pub fn label(&self, offset: usize) -> String {
+        self.granularity.label(self.start + offset as i64)
+    }
+}
+
+pub fn bucket_shows(shows: &[Show], granularity: Granularity) -> Result<PeriodSeries, String> {
+    let mut indexed = Vec::with_capacity(shows.len());
+    for show in shows {
+        let date = Date::parse(&show.date)?;
+        indexed.push((granularity.index_of(&date), show));
+    }
+
+    let start = indexed
+        .iter()
+        .map(|(i, _)| *i)
+        .min()
+        .ok_or("No shows to bucket")?;
+    let end = indexed.iter().map(|(i, _)| *i).max().unwrap_or(start);
+    let len = (end - start + 1) as usize;
+
+    let mut revenue = vec![0.0; len];
+    let mut expenses = vec![0.0; len];
+    for (index, show) in indexed {
+        let slot = (index - start) as usize;
+        revenue[slot] += show.revenue;
+        expenses[slot] += show.expenses;
+    }
+
+    Ok(PeriodSeries {
+        granularity,
+        start,
+        revenue,
+        expenses,
+    })
+}
+
+/// Holdout error of a model, or of the selected model on the final fit
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ModelScore {
+    pub model: String,
+    /// Mean absolute percentage error over periods with non-zero actuals (`None` if all were zero)
+    pub mape: Option<f64>,
+    pub rmse: f64,
+    pub mae: f64,
+    pub holdout_periods: usize,
+}
+
+pub(crate) fn error_metrics(predicted: &[f64], actual: &[f64]) -> (Option<f64>, f64, f64) {
+    let n = predicted.len().min(actual.len()).max(1) as f64;
+    let mut squared = 0.0;
+    let mut absolute = 0.0;
+    let mut percentage = Vec::new();
+    for (p, a) in predicted.iter().zip(actual) {
+        let error = a - p;
+        squared += error * error;
+        absolute += error.abs();
+        if *a != 0.0 {
+            percentage.push((error / a).abs() * 100.0);
+        }
+    }
+    let mape =
+        (!percentage.is_empty()).then(|| percentage.iter().sum::<f64>() / percentage.len() as f64);
+    (mape, (squared / n).sqrt(), absolute / n)
+}
+
+/// Number of trailing periods held out when scoring candidate models
+fn holdout_size(n: usize, season_length: usize) -> usize {
+    (n / 4).clamp(1, season_length)
+}
+
+/// Score each candidate on a trailing holdout and return the one with the lowest RMSE
+pub(crate) fn select_model<'a>(
+    candidates: &'a [Box<dyn ForecastModel>],
+    history: &[f64],
+    start: i64,
+    season_length: usize,
+) -> Result<(&'a dyn ForecastModel, Vec<ModelScore>), String> {
+    let n = history.len();
+    let holdout = holdout_size(n, season_length);
+    let train = &history[..n.saturating_sub(holdout)];
+
+    let mut scores = Vec::new();
+    let mut best: Option<(f64, &dyn ForecastModel)> = None;
+    for model in candidates {
+        if train.len() < model.min_history() {
+            continue;
+        }
+        let fit = model.fit(train, start, holdout);
+        let (mape, rmse, mae) = error_metrics(&fit.forecast, &history[train.len()..]);
+        if best.is_none_or(|(b, _)| rmse < b) {
+            best = Some((rmse, model.as_ref()));
+        }
+        scores.push(ModelScore {
+            model: model.name().to_string(),
+            mape,
+            rmse,
+            mae,
+            holdout_periods: holdout,
+        });
+    }
+
+    match best {
+        Some((_, model)) => Ok((model, scores)),
+        // Too little history to hold anything out: fall back to whichever model fits at all
+        None => candidates
+            .iter()
+            .find(|m| n >= m.min_history())
+            .map(|m| (m.as_ref(), scores))
+            .ok_or_else(|| "Not enough history for the requested forecast model".to_string()),
+    }
+}
+
+/// Forecast revenue, expenses and profit per calendar period from the shows' real dates.
+///
+/// `model` is one of the names in [`models::models_for`]; `"auto"` scores every model on
+/// a trailing holdout and keeps the one with the lowest RMSE.
+pub fn forecast(
+    shows: &[Show],
+    periods_ahead: u32,
+    granularity: Granularity,
+    model: &str,
+) -> Result<ForecastResult, String> {
+    let series = bucket_shows(shows, granularity)?;
+    let n = series.len();
+    let season_length = granularity.season_length();
+    let candidates = models::models_for(model, season_length)?;
+    let (chosen, candidate_scores) =
+        select_model(&candidates, &series.revenue, series.start, season_length)?;
+
+    let horizon = periods_ahead as usize;
+    let fit = chosen.fit(&series.revenue, series.start, horizon);
+
+    let residuals: Vec<f64> = fit
+        .fitted
+        .iter()
+        .zip(&series.revenue)
+        .filter_map(|(f, y)| f.map(|f| y - f))
+        .collect();
+    let residual_std = if residuals.len() > 1 {
+        (residuals.iter().map(|e| e * e).sum::<f64>() / (residuals.len() - 1) as f64).sqrt()
+    } else {
+        0.0
+    };
+
+    let total_revenue: f64 = series.revenue.iter().sum();
+    let total_expenses: f64 = series.expenses.iter().sum();
+    let avg_revenue = total_revenue / n as f64;
+    let expense_ratio = if total_revenue > 0.0 {
+        total_expenses / total_revenue
+    } else {
+        0.7 // Default 70% expense ratio
+    };
+
+    let seasonality_factor = if avg_revenue > 0.0 && !fit.seasonal.is_empty() {
+        let seasonal_std = (fit.seasonal.iter().map(|v| v.powi(2)).sum::<f64>()
+            / fit.seasonal.len() as f64)
+            .sqrt();
+        (seasonal_std / avg_revenue) * 100.0
+    } else {
+        0.0
+    };
+
+    let metrics = candidate_scores
+        .iter()
+        .find(|s| s.model == chosen.name())
+        .cloned()
+        .unwrap_or_else(|| {
+            // No holdout was possible: report in-sample error instead
+            let fitted: Vec<f64> = fit
+                .fitted
+                .iter()
+                .zip(&series.revenue)
+                .map(|(f, y)| f.unwrap_or(*y))
+                .collect();
+            let (mape, rmse, mae) = error_metrics(&fitted, &series.revenue);
+            ModelScore {
+                model: chosen.name().to_string(),
+                mape,
+                rmse,
+                mae,
+                holdout_periods: 0,
+            }
+        });
+
+    let mut result = ForecastResult {
+        projected_revenue: Vec::new(),
+        projected_expenses: Vec::new(),
+        projected_profit: Vec::new(),
+        confidence_interval: Vec::new(),
+        trend_slope: fit.trend,
+        seasonality_factor,
+        periods: Vec::new(),
+        model: chosen.name().to_string(),
+        metrics,
+        candidates: candidate_scores,
+    };
+
+    for (i, projected) in fit.forecast.iter().enumerate() {
+        let h = i + 1;
+        let offset = n - 1 + h;
+        let revenue = projected.max(0.0);
+        let expenses = revenue * expense_ratio;
+        let profit = revenue - expenses;
+
+        let confidence = Z_P90 * residual_std * chosen.interval_scale(h, n);
+        let net_confidence = confidence * (1.0 - expense_ratio).abs();
+
+        result.projected_revenue.push(revenue);
+        result.projected_expenses.push(expenses);
+        result.projected_profit.push(profit);
+        result.confidence_interval.push(confidence);
+        result.periods.push(ForecastPeriod {
+            month: series.label(offset),
+            revenue,
+            expenses,
+            profit,
+            net: profit,
+            p50: profit,
+            p90: profit + net_confidence,
+        });
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn show(date: &str, revenue: f64) -> Show {
+        serde_json::from_value(
+            json!({ "date": date, "revenue": revenue, "expenses": revenue * 0.4 }),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn shows_are_bucketed_into_consecutive_periods() {
+        let shows = [
+            show("2024-11-30", 1000.0),
+            show("2025-02-01", 500.0),
+            show("2024-11-02", 250.0),
+        ];
+        let series = bucket_shows(&shows, Granularity::Month).unwrap();
+        assert_eq!(series.revenue, [1250.0, 0.0, 0.0, 500.0]);
+        assert_eq!(series.expenses[0], 500.0);
+        assert_eq!(series.label(0), "2024-11");
+        assert_eq!(series.label(3), "2025-02");
+
+        // 2024-12-30 falls in the first ISO week of 2025
+        let weekly = bucket_shows(&[show("2024-12-30", 1.0)], Granularity::Week).unwrap();
+        assert_eq!(weekly.label(0), "2025-W01");
+        assert!(bucket_shows(&[], Granularity::Month).is_err());
+    }
+
+    #[test]
+    fn error_metrics_skip_zero_actuals_for_mape() {
+        let (mape, rmse, mae) = error_metrics(&[110.0, 0.0, 5.0], &[100.0, 0.0, 0.0]);
+        assert_eq!(mape, Some(10.0));
+        assert!((rmse - (125.0f64 / 3.0).sqrt()).abs() < 1e-12);
+        assert_eq!(mae, 5.0);
+        assert_eq!(error_metrics(&[1.0], &[0.0]).0, None);
+    }
+
+    #[test]
+    fn forecast_continues_a_trend_from_the_last_period() {
+        let shows: Vec<Show> = (0..8)
+            .map(|m| show(&format!("2025-{:02}-10", m + 1), 1000.0 + 100.0 * m as f64))
+            .collect();
+        let result = forecast(&shows, 3, Granularity::Month, "linear_seasonal").unwrap();
+        assert_eq!(result.model, "linear_seasonal");
+        let labels: Vec<&str> = result.periods.iter().map(|p| p.month.as_str()).collect();
+        assert_eq!(labels, ["2025-09", "2025-10", "2025-11"]);
+        for (i, revenue) in result.projected_revenue.iter().enumerate() {
+            assert!((revenue - (1800.0 + 100.0 * i as f64)).abs() < 1e-6);
+        }
+        assert!((result.trend_slope - 100.0).abs() < 1e-9);
+        assert!((result.periods[0].expenses - 720.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn auto_selects_the_lowest_holdout_error() {
+        let shows: Vec<Show> = (0..12)
+            .map(|m| show(&format!("2025-{:02}-10", m + 1), 1000.0 + 100.0 * m as f64))
+            .collect();
+        let result = forecast(&shows, 1, Granularity::Month, "auto").unwrap();
+        let best = result
+            .candidates
+            .iter()
+            .map(|c| c.rmse)
+            .fold(f64::INFINITY, f64::min);
+        assert_eq!(result.metrics.rmse, best);
+        assert!(forecast(&shows, 1, Granularity::Month, "arima").is_err());
+    }
+}
@@ -0,0 +1,231 @@
+use serde::{Deserialize, Serialize};
+
+use super::{bucket_shows, error_metrics, models, select_model, Granularity};
+use crate::date::Date;
+use crate::Show;
+
+fn default_min_train_shows() -> usize {
+    3
+}
+
+fn default_horizon() -> usize {
+    3
+}
+
+fn default_step() -> usize {
+    1
+}
+
+fn default_granularity() -> Granularity {
+    Granularity::Month
+}
+
+fn default_model() -> String {
+    "auto".to_string()
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BacktestOptions {
+    /// Shows in the first training window; each later origin adds `step` more shows
+    #[serde(default = "default_min_train_shows")]
+    pub min_train_shows: usize,
+    #[serde(default = "default_horizon")]
+    pub horizon: usize,
+    #[serde(default = "default_step")]
+    pub step: usize,
+    #[serde(default = "default_granularity")]
+    pub granularity: Granularity,
+    #[serde(default = "default_model")]
+    pub model: String,
+}
+
+/// Error statistics for all forecasts made `horizon` periods past their origin
+#[derive(Serialize, Deserialize)]
+pub struct HorizonStats {
+    pub horizon: usize,
+    pub count: usize,
+    pub mape: Option<f64>,
+    pub rmse: f64,
+    pub mae: f64,
+    /// Mean of predicted minus actual; positive means the model over-forecasts
+    pub bias: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BacktestPoint {
+    /// Last period included in the training window
+    pub origin: String,
+    pub train_shows: usize,
+    pub model: String,
+    pub horizon: usize,
+    pub period: String,
+    pub predicted: f64,
+    pub actual: f64,
+    pub error: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BacktestResult {
+    pub granularity: Granularity,
+    pub model: String,
+    pub origins: usize,
+    pub horizons: Vec<HorizonStats>,
+    pub series: Vec<BacktestPoint>,
+}
+
+/// Rolling-origin evaluation of the revenue forecast.
+///
+/// Training windows are snapped to whole periods so a month is never split between
+/// training and evaluation: the origin is the period of the Nth show by date, and every
+/// show in or before that period is used for training.
+pub fn backtest(shows: &[Show], options: &BacktestOptions) -> Result<BacktestResult, String> {
+    if options.horizon == 0 {
+        return Err("horizon must be greater than zero".to_string());
+    }
+    if options.min_train_shows == 0 || options.step == 0 {
+        return Err("min_train_shows and step must be greater than zero".to_string());
+    }
+
+    let granularity = options.granularity;
+    let season_length = granularity.season_length();
+    let candidates = models::models_for(&options.model, season_length)?;
+    let full = bucket_shows(shows, granularity)?;
+    if options.horizon > full.len() {
+        return Err(format!(
+            "horizon of {} periods is longer than the {} periods of history",
+            options.horizon,
+            full.len()
+        ));
+    }
+    let last_offset = full.len() - 1;
+
+    let mut show_offsets = Vec::with_capacity(shows.len());
+    for show in shows {
+        let date = Date::parse(&show.date)?;
+        show_offsets.push((granularity.index_of(&date) - full.start) as usize);
+    }
+    show_offsets.sort_unstable();
+
+    let mut series = Vec::new();
+    let mut origins = 0;
+    let mut previous_origin = None;
+    for n in (options.min_train_shows..show_offsets.len()).step_by(options.step) {
+        let origin = show_offsets[n - 1];
+        if origin >= last_offset || previous_origin == Some(origin) || origin < 1 {
+            continue;
+        }
+        previous_origin = Some(origin);
+
+        let train = &full.revenue[..=origin];
+        let train_shows = show_offsets.iter().filter(|&&o| o <= origin).count();
+        let (model, _) = match select_model(&candidates, train, full.start, season_length) {
+            Ok(selection) => selection,
+            Err(_) => continue,
+        };
+        let fit = model.fit(train, full.start, options.horizon);
+        origins += 1;
+
+        for (i, predicted) in fit.forecast.iter().enumerate() {
+            let offset = origin + i + 1;
+            if offset > last_offset {
+                break;
+            }
+            let predicted = predicted.max(0.0);
+            let actual = full.revenue[offset];
+            series.push(BacktestPoint {
+                origin: full.label(origin),
+                train_shows,
+                model: model.name().to_string(),
+                horizon: i + 1,
+                period: full.label(offset),
+                predicted,
+                actual,
+                error: predicted - actual,
+            });
+        }
+    }
+
+    if series.is_empty() {
+        return Err(
+            "Not enough history to backtest: need shows beyond the first training window"
+                .to_string(),
+        );
+    }
+
+    let horizons = (1..=options.horizon)
+        .filter_map(|h| {
+            let points: Vec<&BacktestPoint> = series.iter().filter(|p| p.horizon == h).collect();
+            if points.is_empty() {
+                return None;
+            }
+            let predicted: Vec<f64> = points.iter().map(|p| p.predicted).collect();
+            let actual: Vec<f64> = points.iter().map(|p| p.actual).collect();
+            let (mape, rmse, mae) = error_metrics(&predicted, &actual);
+            Some(HorizonStats {
+                horizon: h,
+                count: points.len(),
+                mape,
+                rmse,
+                mae,
+                bias: points.iter().map(|p| p.error).sum::<f64>() / points.len() as f64,
+            })
+        })
+        .collect();
+
+    Ok(BacktestResult {
+        granularity,
+        model: options.model.clone(),
+        origins,
+        horizons,
+        series,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// One show on the 15th of each month from January 2023, with steadily rising revenue
+    fn monthly_shows(months: usize) -> Vec<Show> {
+        (0..months)
+            .map(|m| {
+                serde_json::from_value(json!({
+                    "date": format!("{:04}-{:02}-15", 2023 + m / 12, m % 12 + 1),
+                    "revenue": 10000 + 500 * m,
+                    "expenses": 4000,
+                }))
+                .unwrap()
+            })
+            .collect()
+    }
+
+    fn options(horizon: usize) -> BacktestOptions {
+        serde_json::from_value(json!({ "horizon": horizon, "model": "linear_seasonal" })).unwrap()
+    }
+
+    #[test]
+    fn rolling_origins_cover_each_horizon() {
+        let result = backtest(&monthly_shows(12), &options(3)).unwrap();
+        assert_eq!(result.horizons.len(), 3);
+        assert_eq!(result.series[0].origin, "2023-03");
+        assert_eq!(result.series[0].period, "2023-04");
+        assert!(result.series.iter().all(|p| p.horizon <= 3));
+        // A straight line is forecast exactly
+        assert!(result.horizons.iter().all(|h| h.mae < 1e-6));
+    }
+
+    #[test]
+    fn horizon_is_bounded_by_the_history() {
+        let shows = monthly_shows(12);
+        assert!(backtest(&shows, &options(0)).is_err());
+        assert!(backtest(&shows, &options(12)).is_ok());
+        assert!(backtest(&shows, &options(13)).is_err());
+        assert!(backtest(&shows, &options(usize::MAX)).is_err());
+    }
+
+    #[test]
+    fn too_little_history_is_an_error() {
+        assert!(backtest(&monthly_shows(3), &options(1)).is_err());
+    }
+}
@@ -0,0 +1,354 @@
+/// Smoothing parameter grid searched when fitting exponential smoothing models
+const SMOOTHING_GRID: [f64; 5] = [0.1, 0.3, 0.5, 0.7, 0.9];
+const DAMPING_GRID: [f64; 3] = [0.8, 0.9, 0.98];
+
+/// Output of fitting a model to a history and projecting it forward
+pub struct Fit {
+    /// In-sample predictions aligned with the history (`None` while the model warms up)
+    pub fitted: Vec<Option<f64>>,
+    pub forecast: Vec<f64>,
+    /// Per-period trend at the end of the history
+    pub trend: f64,
+    /// Additive seasonal offsets, empty for non-seasonal models
+    pub seasonal: Vec<f64>,
+}
+
+impl Fit {
+    /// Sum of squared in-sample errors, used to tune smoothing parameters
+    fn sse(&self, history: &[f64]) -> f64 {
+        self.fitted
+            .iter()
+            .zip(history)
+            .filter_map(|(f, y)| f.map(|f| (y - f).powi(2)))
+            .sum()
+    }
+}
+
+pub trait ForecastModel {
+    fn name(&self) -> &'static str;
+
+    /// Minimum number of periods the model needs to produce a fit
+    fn min_history(&self) -> usize;
+
+    /// Fit to `history` (whose first period has sequential index `start`) and project `horizon` periods
+    fn fit(&self, history: &[f64], start: i64, horizon: usize) -> Fit;
+
+    /// Growth of the prediction interval `h` periods past a history of `n` periods
+    fn interval_scale(&self, h: usize, _n: usize) -> f64 {
+        (h as f64).sqrt()
+    }
+}
+
+/// Least squares trend plus additive seasonal offsets (once a full cycle is available)
+pub struct LinearSeasonal {
+    pub season_length: usize,
+}
+
+/// Holt-Winters triple exponential smoothing with additive seasonality
+pub struct HoltWinters {
+    pub season_length: usize,
+}
+
+/// Flat projection of the mean of the last `window` periods
+pub struct MovingAverage {
+    pub window: usize,
+}
+
+/// Holt's linear trend with a damping factor so long horizons level off
+pub struct DampedTrend;
+
+/// Ordinary least squares fit of `values` against their period offset
+fn linear_trend(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    if values.len() < 2 {
+        return (values.first().copied().unwrap_or(0.0), 0.0);
+    }
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = values.iter().sum::<f64>() / n;
+    let (mut sxy, mut sxx) = (0.0, 0.0);
+    for (i, y) in values.iter().enumerate() {
+        let dx = i as f64 - mean_x;
+        sxy += dx * (y - mean_y);
+        sxx += dx * dx;
+    }
+    let slope = sxy / sxx;
+    (mean_y - slope * mean_x, slope)
+}
+
+impl ForecastModel for LinearSeasonal {
+    fn name(&self) -> &'static str {
+        "linear_seasonal"
+    }
+
+    fn min_history(&self) -> usize {
+        2
+    }
+
+    fn fit(&self, history: &[f64], start: i64, horizon: usize) -> Fit {
+        let n = history.len();
+        let m = self.season_length;
+        let (intercept, slope) = linear_trend(history);
+        let trend_at = |t: usize| intercept + slope * t as f64;
+        let position = |t: usize| (start + t as i64).rem_euclid(m as i64) as usize;
+
+        let mut seasonal = Vec::new();
+        if n >= m {
+            seasonal = vec![0.0; m];
+            let mut counts = vec![0usize; m];
+            for (t, y) in history.iter().enumerate() {
+                seasonal[position(t)] += y - trend_at(t);
+                counts[position(t)] += 1;
+            }
+            for (value, count) in seasonal.iter_mut().zip(&counts) {
+                if *count > 0 {
+                    *value /= *count as f64;
+                }
+            }
+            // Offsets should not shift the level of the trend
+            let mean_offset = seasonal.iter().sum::<f64>() / m as f64;
+            seasonal.iter_mut().for_each(|v| *v -= mean_offset);
+        }
+        let season_at = |t: usize| seasonal.get(position(t)).copied().unwrap_or(0.0);
+
+        Fit {
+            fitted: (0..n).map(|t| Some(trend_at(t) + season_at(t))).collect(),
+            forecast: (n..n + horizon)
+                .map(|t| trend_at(t) + season_at(t))
+                .collect(),
+            trend: slope,
+            seasonal: seasonal.clone(),
+        }
+    }
+
+    fn interval_scale(&self, h: usize, n: usize) -> f64 {
+        (1.0 + h as f64 / n.max(1) as f64).sqrt()
+    }
+}
+
+impl HoltWinters {
+    fn fit_with(&self, history: &[f64], horizon: usize, alpha: f64, beta: f64, gamma: f64) -> Fit {
+        let n = history.len();
+        let m = self.season_length;
+
+        // Initial level/trend from the first two seasons, seasonal offsets from the first
+        let first_mean = history[..m].iter().sum::<f64>() / m as f64;
+        let second_mean = history[m..2 * m].iter().sum::<f64>() / m as f64;
+        let mut level = first_mean;
+        let mut trend = (second_mean - first_mean) / m as f64;
+        let mut seasonal: Vec<f64> = history[..m].iter().map(|y| y - first_mean).collect();
+
+        let mut fitted = vec![None; n];
+        for t in m..n {
+            let s = seasonal[t % m];
+            fitted[t] = Some(level + trend + s);
+            let previous_level = level;
+            level = alpha * (history[t] - s) + (1.0 - alpha) * (level + trend);
+            trend = beta * (level - previous_level) + (1.0 - beta) * trend;
+            seasonal[t % m] = gamma * (history[t] - level) + (1.0 - gamma) * s;
+        }
+
+        let forecast = (1..=horizon)
+            .map(|h| level + h as f64 * trend + seasonal[(n + h - 1) % m])
+            .collect();
+
+        Fit {
+            fitted,
+            forecast,
+            trend,
+            seasonal,
+        }
+    }
+}
+
+impl ForecastModel for HoltWinters {
+    fn name(&self) -> &'static str {
+        "holt_winters"
+    }
+
+    fn min_history(&self) -> usize {
+        2 * self.season_length
+    }
+
+    fn fit(&self, history: &[f64], _start: i64, horizon: usize) -> Fit {
+        let mut best: Option<(f64, Fit)> = None;
+        for &alpha in &SMOOTHING_GRID {
+            for &beta in &SMOOTHING_GRID {
+                for &gamma in &SMOOTHING_GRID {
+                    let fit = self.fit_with(history, horizon, alpha, beta, gamma);
+                    let sse = fit.sse(history);
+                    if best.as_ref().is_none_or(|(b, _)| sse < *b) {
+                        best = Some((sse, fit));
+                    }
+                }
+            }
+        }
+        best.map(|(_, fit)| fit)
+            .unwrap_or_else(|| self.fit_with(history, horizon, 0.5, 0.1, 0.1))
+    }
+}
+
+impl ForecastModel for MovingAverage {
+    fn name(&self) -> &'static str {
+        "moving_average"
+    }
+
+    fn min_history(&self) -> usize {
+        self.window.max(1)
+    }
+
+    fn fit(&self, history: &[f64], _start: i64, horizon: usize) -> Fit {
+        let w = self.window.max(1).min(history.len());
+        let mean = |slice: &[f64]| slice.iter().sum::<f64>() / slice.len() as f64;
+
+        let fitted = (0..history.len())
+            .map(|t| (t >= w).then(|| mean(&history[t - w..t])))
+            .collect();
+        let last = mean(&history[history.len() - w..]);
+
+        Fit {
+            fitted,
+            forecast: vec![last; horizon],
+            trend: 0.0,
+            seasonal: Vec::new(),
+        }
+    }
+}
+
+impl DampedTrend {
+    fn fit_with(history: &[f64], horizon: usize, alpha: f64, beta: f64, phi: f64) -> Fit {
+        let n = history.len();
+        let mut level = history[0];
+        let mut trend = history[1] - history[0];
+
+        let mut fitted = vec![None; n];
+        for t in 1..n {
+            fitted[t] = Some(level + phi * trend);
+            let previous_level = level;
+            level = alpha * history[t] + (1.0 - alpha) * (level + phi * trend);
+            trend = beta * (level - previous_level) + (1.0 - beta) * phi * trend;
+        }
+
+        let mut damping = 0.0;
+        let forecast = (1..=horizon as i32)
+            .map(|h| {
+                damping += phi.powi(h);
+                level + damping * trend
+            })
+            .collect();
+
+        Fit {
+            fitted,
+            forecast,
+            trend,
+            seasonal: Vec::new(),
+        }
+    }
+}
+
+impl ForecastModel for DampedTrend {
+    fn name(&self) -> &'static str {
+        "damped_trend"
+    }
+
+    fn min_history(&self) -> usize {
+        3
+    }
+
+    fn fit(&self, history: &[f64], _start: i64, horizon: usize) -> Fit {
+        let mut best: Option<(f64, Fit)> = None;
+        for &alpha in &SMOOTHING_GRID {
+            for &beta in &SMOOTHING_GRID {
+                for &phi in &DAMPING_GRID {
+                    let fit = DampedTrend::fit_with(history, horizon, alpha, beta, phi);
+                    let sse = fit.sse(history);
+                    if best.as_ref().is_none_or(|(b, _)| sse < *b) {
+                        best = Some((sse, fit));
+                    }
+                }
+            }
+        }
+        best.map(|(_, fit)| fit)
+            .unwrap_or_else(|| DampedTrend::fit_with(history, horizon, 0.5, 0.1, 0.9))
+    }
+}
+
+/// Look up a model by name, or every candidate model for `"auto"`
+pub fn models_for(name: &str, season_length: usize) -> Result<Vec<Box<dyn ForecastModel>>, String> {
+    let all: Vec<Box<dyn ForecastModel>> = vec![
+        Box::new(LinearSeasonal { season_length }),
+        Box::new(HoltWinters { season_length }),
+        Box::new(MovingAverage { window: 3 }),
+        Box::new(DampedTrend),
+    ];
+    if name == "auto" {
+        return Ok(all);
+    }
+    let selected: Vec<_> = all.into_iter().filter(|m| m.name() == name).collect();
+    if selected.is_empty() {
+        return Err(format!(
+            "Unknown forecast model '{}': expected auto, linear_seasonal, holt_winters, moving_average or damped_trend",
+            name
+        ));
+    }
+    Ok(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_trend_fits_a_line_exactly() {
+        assert_eq!(linear_trend(&[3.0, 5.0, 7.0, 9.0]), (3.0, 2.0));
+        assert_eq!(linear_trend(&[4.0]), (4.0, 0.0));
+    }
+
+    #[test]
+    fn linear_seasonal_recovers_offsets() {
+        // Flat level of 100 with +10/-10 in alternating periods
+        let history: Vec<f64> = (0..8)
+            .map(|t| if t % 2 == 0 { 110.0 } else { 90.0 })
+            .collect();
+        let fit = LinearSeasonal { season_length: 2 }.fit(&history, 0, 2);
+        assert!(fit.trend.abs() < 1.5);
+        assert_eq!(fit.seasonal.len(), 2);
+        assert!(fit.forecast[0] > 100.0 && fit.forecast[1] < 100.0);
+    }
+
+    #[test]
+    fn moving_average_is_flat() {
+        let fit = MovingAverage { window: 3 }.fit(&[1.0, 2.0, 3.0, 4.0, 5.0], 0, 2);
+        assert_eq!(fit.forecast, [4.0, 4.0]);
+        assert_eq!(fit.fitted[3], Some(2.0));
+        assert_eq!(fit.fitted[2], None);
+    }
+
+    #[test]
+    fn damped_trend_levels_off() {
+        let history: Vec<f64> = (0..10).map(|t| 100.0 + 10.0 * t as f64).collect();
+        let fit = DampedTrend.fit(&history, 0, 24);
+        let steps: Vec<f64> = fit.forecast.windows(2).map(|w| w[1] - w[0]).collect();
+        assert!(steps[0] > 0.0);
+        assert!(steps.windows(2).all(|s| s[1] <= s[0] + 1e-9));
+        assert!(fit.forecast[23] < 190.0 + 24.0 * 10.0);
+    }
+
+    #[test]
+    fn holt_winters_tracks_a_repeating_season() {
+        let history: Vec<f64> = (0..12).map(|t| [50.0, 100.0, 150.0][t % 3]).collect();
+        let model = HoltWinters { season_length: 3 };
+        assert_eq!(model.min_history(), 6);
+        let fit = model.fit(&history, 0, 3);
+        for (forecast, expected) in fit.forecast.iter().zip([50.0, 100.0, 150.0]) {
+            assert!((forecast - expected).abs() < 1.0);
+        }
+    }
+
+    #[test]
+    fn models_are_looked_up_by_name() {
+        assert_eq!(models_for("auto", 12).unwrap().len(), 4);
+        let selected = models_for("damped_trend", 12).unwrap();
+        assert_eq!(selected[0].name(), "damped_trend");
+        assert!(models_for("prophet", 12).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::date::Date;
+use crate::money::{Currency, Money, RoundingMode};
+
+/// Rates in effect from `date` (`YYYY-MM-DD`, or `YYYY-MM` for the first of the month):
+/// units of each currency per one unit of `base`
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FxRateTable {
+    pub date: String,
+    #[serde(default)]
+    pub base: Currency,
+    pub rates: HashMap<Currency, f64>,
+}
+
+struct DatedRates {
+    date: Date,
+    base: Currency,
+    rates: HashMap<Currency, f64>,
+}
+
+impl DatedRates {
+    /// Units of `currency` per one unit of the table's base
+    fn per_base(&self, currency: Currency) -> Option<f64> {
+        if currency == self.base {
+            Some(1.0)
+        } else {
+            self.rates.get(&currency).copied()
+        }
+    }
+}
+
+/// Dated FX tables, looked up with nearest-previous-date fallback
+#[derive(Default)]
+pub struct FxRates {
+    tables: Vec<DatedRates>,
+}
+
+fn parse_table_date(value: &str) -> Result<Date, String> {
+    if value.len() == 7 {
+        Date::parse(&format!("{}-01", value))
+    } else {
+        Date::parse(value)
+    }
+}
+
+impl FxRates {
+    pub fn from_tables(tables: Vec<FxRateTable>) -> Result<FxRates, String> {
+        let mut dated = Vec::with_capacity(tables.len());
+        for table in tables {
+            if let Some((currency, rate)) = table
+                .rates
+                .iter()
+                .find(|(_, rate)| !rate.is_finite() || **rate <= 0.0)
+            {
+                return Err(format!(
+                    "Invalid FX rate {} for {} on {}",
+                    rate, currency, table.date
+                ));
+            }
+            dated.push(DatedRates {
+                date: parse_table_date(&table.date)?,
+                base: table.base,
+                rates: table.rates,
+            });
+        }
+        dated.sort_by_key(|t| t.date);
+        Ok(FxRates { tables: dated })
+    }
+
+    pub fn len(&self) -> usize {
+        self.tables.len()
+    }
+
+    /// Units of `to` per one unit of `from`, using the latest table on or before `date`
+    /// that quotes both currencies
+    pub fn rate(&self, from: Currency, to: Currency, date: &Date) -> Result<f64, String> {
+        if from == to {
+            return Ok(1.0);
+        }
+        self.tables
+            .iter()
+            .rev()
+            .filter(|t| t.date <= *date)
+            .find_map(|t| Some(t.per_base(to)? / t.per_base(from)?))
+            .ok_or_else(|| format!("No FX rate for {}->{} on or before {}", from, to, date))
+    }
+
+    pub fn convert(
+        &self,
+        amount: Money,
+        to: Currency,
+        date: &Date,
+        mode: RoundingMode,
+    ) -> Result<Money, String> {
+        if amount.currency() == to {
+            return Ok(amount);
+        }
+        let rate = self.rate(amount.currency(), to, date)?;
+        // Apply the rate to the minor units directly, adjusting for differing decimals
+        let decimals = to.decimals() as i32 - amount.currency().decimals() as i32;
+        let factor = rate * 10f64.powi(decimals);
+        Money::from_minor_units(amount.minor_units(), to).multiply(factor, mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rates() -> FxRates {
+        FxRates::from_tables(
+            serde_json::from_value(json!([
+                { "date": "2025-03", "rates": { "USD": 1.10, "GBP": 0.85 } },
+                { "date": "2025-01-01", "rates": { "USD": 1.05, "JPY": 160 } }
+            ]))
+            .unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn currency(code: &str) -> Currency {
+        Currency::parse(code).unwrap()
+    }
+
+    fn date(value: &str) -> Date {
+        Date::parse(value).unwrap()
+    }
+
+    #[test]
+    fn rates_come_from_the_latest_table_on_or_before_the_date() {
+        let rates = rates();
+        let (eur, usd) = (currency("EUR"), currency("USD"));
+        assert_eq!(rates.rate(eur, usd, &date("2025-02-28")), Ok(1.05));
+        assert_eq!(rates.rate(eur, usd, &date("2025-03-01")), Ok(1.10));
+        assert!(rates.rate(eur, usd, &date("2024-12-31")).is_err());
+        assert_eq!(rates.rate(usd, usd, &date("2000-01-01")), Ok(1.0));
+    }
+
+    #[test]
+    fn cross_rates_fall_back_to_tables_quoting_both_currencies() {
+        let rates = rates();
+        let cross = rates.rate(currency("USD"), currency("GBP"), &date("2025-04-01"));
+        assert!((cross.unwrap() - 0.85 / 1.10).abs() < 1e-12);
+        // Only the January table has yen
+        let yen = rates.rate(currency("EUR"), currency("JPY"), &date("2025-04-01"));
+        assert_eq!(yen, Ok(160.0));
+    }
+
+    #[test]
+    fn conversion_adjusts_for_minor_units() {
+        let rates = rates();
+        let mode = RoundingMode::default();
+        let eur = Money::parse("12.34", currency("EUR"), mode).unwrap();
+        let yen = rates.convert(eur, currency("JPY"), &date("2025-02-01"), mode);
+        assert_eq!(yen.unwrap().minor_units(), 1974);
+        let usd = rates.convert(eur, currency("USD"), &date("2025-02-01"), mode);
+        assert_eq!(usd.unwrap().minor_units(), 1296);
+    }
+
+    #[test]
+    fn non_positive_rates_are_rejected() {
+        let tables = serde_json::from_value(json!([{ "date": "2025-01", "rates": { "USD": 0 } }]));
+        assert!(FxRates::from_tables(tables.unwrap()).is_err());
+    }
+}
@@ -0,0 +1,359 @@
+use serde::{Deserialize, Serialize};
+
+use crate::schedule::{CascadeEntry, EntityKind};
+use crate::status::{ChangeType, EntityStatus, ReleaseStatus, ShowStatus, TaskStatus};
+use crate::{TimelineChange, TimelineData};
+
+/// Snapshots kept for undo; the oldest are dropped beyond this
+const HISTORY_LIMIT: usize = 100;
+
+/// A committed state of the timeline
+pub struct Snapshot {
+    pub version: u32,
+    pub data: TimelineData,
+    /// The change that produced this state, `None` for freshly loaded data
+    pub change: Option<TimelineChange>,
+}
+
+/// Undo/redo stacks of whole-timeline snapshots
+#[derive(Default)]
+pub struct History {
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    latest_version: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HistoryEntry {
+    pub version: u32,
+    pub change: Option<TimelineChange>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HistoryState {
+    pub version: u32,
+    pub can_undo: bool,
+    pub can_redo: bool,
+    /// Committed states, oldest first, ending with the current one
+    pub undo: Vec<HistoryEntry>,
+    /// States that redo would restore, next first
+    pub redo: Vec<HistoryEntry>,
+}
+
+fn entry(snapshot: &Snapshot) -> HistoryEntry {
+    HistoryEntry {
+        version: snapshot.version,
+        change: snapshot.change.clone(),
+    }
+}
+
+impl History {
+    /// Record `previous` before a new change is committed and return the new version
+    pub fn commit(&mut self, previous: Snapshot) -> u32 {
+        self.undo.push(previous);
+        if self.undo.len() > HISTORY_LIMIT {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+        self.latest_version += 1;
+        self.latest_version
+    }
+
+    /// Swap `current` for the previous snapshot; false when there is nothing to undo
+    pub fn undo(&mut self, current: &mut Snapshot) -> bool {
+        match self.undo.pop() {
+            Some(previous) => {
+                self.redo.push(std::mem::replace(current, previous));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Swap `current` for the most recently undone snapshot; false when there is none
+    pub fn redo(&mut self, current: &mut Snapshot) -> bool {
+        match self.redo.pop() {
+            Some(next) => {
+                self.undo.push(std::mem::replace(current, next));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Undo and redo stacks around the current state, which is at `version` after `change`
+    pub fn state(&self, version: u32, change: Option<&TimelineChange>) -> HistoryState {
+        let mut undo: Vec<HistoryEntry> = self.undo.iter().map(entry).collect();
+        undo.push(HistoryEntry {
+            version,
+            change: change.cloned(),
+        });
+        HistoryState {
+            version,
+            can_undo: !self.undo.is_empty(),
+            can_redo: !self.redo.is_empty(),
+            undo,
+            redo: self.redo.iter().rev().map(entry).collect(),
+        }
+    }
+}
+
+/// Commit a change to the timeline: update the changed entity, then move each dependent
+/// the cascade pushed back. Cancelled entities keep their record and links, marked cancelled.
+pub fn apply_change(
+    data: &mut TimelineData,
+    change: &TimelineChange,
+    cascade: &[CascadeEntry],
+) -> Result<(), String> {
+    let id = change.entity_id.as_str();
+    let cancel = change.change_type == ChangeType::Cancel;
+    let complete = change.change_type == ChangeType::Complete;
+    let date = change
+        .new_date
+        .as_ref()
+        .filter(|_| change.change_type.moves_date());
+    let mismatch = || {
+        let given = change.new_status.map_or(change.entity_type, |s| s.kind());
+        format!(
+            "A {} status cannot be set on a {}",
+            given.as_str(),
+            change.entity_type.as_str()
+        )
+    };
+
+    match change.entity_type {
+        EntityKind::Task => {
+            if let Some(task) = data.tasks.iter_mut().find(|t| t.id == id) {
+                if let Some(date) = date {
+                    task.deadline = date.clone();
+                }
+                if let Some(completion) = change.new_completion {
+                    task.completion_percentage = completion.clamp(0.0, 100.0);
+                }
+                if complete {
+                    task.status = TaskStatus::Completed;
+                    task.completion_percentage = 100.0;
+                }
+                if cancel {
+                    task.status = TaskStatus::Cancelled;
+                }
+                match change.new_status {
+                    Some(EntityStatus::Task(status)) => task.status = status,
+                    Some(_) => return Err(mismatch()),
+                    None => {}
+                }
+            }
+        }
+        EntityKind::Release => {
+            if let Some(release) = data.releases.iter_mut().find(|r| r.id == id) {
+                if let Some(date) = date {
+                    release.release_date = date.clone();
+                }
+                if complete {
+                    release.status = ReleaseStatus::Released;
+                }
+                if cancel {
+                    release.status = ReleaseStatus::Cancelled;
+                }
+                match change.new_status {
+                    Some(EntityStatus::Release(status)) => release.status = status,
+                    Some(_) => return Err(mismatch()),
+                    None => {}
+                }
+            }
+        }
+        EntityKind::Show => {
+            if let Some(show) = data.shows.iter_mut().find(|s| s.id == id) {
+                if let Some(date) = date {
+                    show.date = date.clone();
+                }
+                if complete {
+                    show.status = ShowStatus::Completed;
+                }
+                if cancel {
+                    show.status = ShowStatus::Cancelled;
+                }
+                match change.new_status {
+                    Some(EntityStatus::Show(status)) => show.status = status,
+                    Some(_) => return Err(mismatch()),
+                    None => {}
+                }
+            }
+        }
+    }
+
+    for moved in cascade.iter().filter(|c| c.slip_days > 0) {
+        let new_date = moved.new_date.clone();
+        match moved.entity_type {
+            EntityKind::Task => {
+                if let Some(task) = data.tasks.iter_mut().find(|t| t.id == moved.id) {
+                    task.deadline = new_date;
+                }
+            }
+            EntityKind::Release => {
+                if let Some(release) = data.releases.iter_mut().find(|r| r.id == moved.id) {
+                    release.release_date = new_date;
+                }
+            }
+            EntityKind::Show => {
+                if let Some(show) = data.shows.iter_mut().find(|s| s.id == moved.id) {
+                    show.date = new_date;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::tests::{release, show, task};
+    use serde_json::json;
+
+    fn data() -> TimelineData {
+        TimelineData {
+            tasks: vec![
+                task("mix", "2025-01-08", 16.0, &[]),
+                task("master", "2025-01-09", 8.0, &["mix"]),
+            ],
+            releases: vec![release("single", "2025-01-10", &["master"])],
+            shows: Vec::new(),
+        }
+    }
+
+    fn change(value: serde_json::Value) -> TimelineChange {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn snapshot(version: u32) -> Snapshot {
+        Snapshot {
+            version,
+            data: data(),
+            change: None,
+        }
+    }
+
+    fn cascade(id: &str, entity_type: EntityKind, new_date: &str, slip_days: i64) -> CascadeEntry {
+        CascadeEntry {
+            entity: format!("{}:{}", entity_type.as_str(), id),
+            id: id.to_string(),
+            entity_type,
+            old_date: String::new(),
+            new_date: new_date.to_string(),
+            slip_days,
+            depth: 1,
+        }
+    }
+
+    #[test]
+    fn undo_and_redo_swap_snapshots() {
+        let mut history = History::default();
+        let mut current = snapshot(0);
+        let version = history.commit(std::mem::replace(&mut current, snapshot(0)));
+        current.version = version;
+        assert_eq!(version, 1);
+
+        assert!(history.undo(&mut current));
+        assert_eq!(current.version, 0);
+        assert!(!history.undo(&mut current));
+        let state = history.state(current.version, None);
+        assert!(!state.can_undo && state.can_redo);
+        assert_eq!(state.redo[0].version, 1);
+
+        assert!(history.redo(&mut current));
+        assert_eq!(current.version, 1);
+        assert!(!history.redo(&mut current));
+    }
+
+    #[test]
+    fn new_commits_clear_redo_and_keep_the_limit() {
+        let mut history = History::default();
+        let mut current = snapshot(0);
+        for _ in 0..HISTORY_LIMIT + 5 {
+            let version = history.commit(std::mem::replace(&mut current, snapshot(0)));
+            current.version = version;
+        }
+        let state = history.state(current.version, None);
+        assert_eq!(state.undo.len(), HISTORY_LIMIT + 1);
+        assert_eq!(state.undo[0].version, 5);
+
+        history.undo(&mut current);
+        history.commit(std::mem::replace(&mut current, snapshot(0)));
+        assert!(!history.state(0, None).can_redo);
+    }
+
+    #[test]
+    fn applying_a_delay_moves_the_cascade() {
+        let mut data = data();
+        let delay = change(json!({
+            "change_type": "delay", "entity_type": "task", "entity_id": "mix",
+            "new_date": "2025-01-13", "new_status": "blocked", "new_completion": 150
+        }));
+        let moved = [
+            cascade("master", EntityKind::Task, "2025-01-14", 3),
+            cascade("single", EntityKind::Release, "2025-01-10", 0),
+        ];
+        apply_change(&mut data, &delay, &moved).unwrap();
+        assert_eq!(data.tasks[0].deadline, "2025-01-13");
+        assert_eq!(data.tasks[0].status, TaskStatus::Blocked);
+        assert_eq!(data.tasks[0].completion_percentage, 100.0);
+        assert_eq!(data.tasks[1].deadline, "2025-01-14");
+        assert_eq!(data.releases[0].release_date, "2025-01-10");
+    }
+
+    #[test]
+    fn cancelling_keeps_the_entity_and_links_to_it() {
+        let mut data = data();
+        let cancel = change(json!({
+            "change_type": "cancel", "entity_type": "task", "entity_id": "master"
+        }));
+        apply_change(&mut data, &cancel, &[]).unwrap();
+        assert_eq!(data.tasks.len(), 2);
+        assert_eq!(data.tasks[1].status, TaskStatus::Cancelled);
+        assert_eq!(data.releases[0].dependencies, vec!["master"]);
+
+        // The exported record still carries the cancelled task for a later undo
+        let exported = serde_json::to_value(&data).unwrap();
+        assert_eq!(exported["tasks"][1]["id"], "master");
+        assert_eq!(exported["tasks"][1]["status"], "cancelled");
+
+        let release = change(json!({
+            "change_type": "complete", "entity_type": "release", "entity_id": "single"
+        }));
+        apply_change(&mut data, &release, &[]).unwrap();
+        assert_eq!(data.releases[0].status, ReleaseStatus::Released);
+    }
+
+    #[test]
+    fn completing_a_show_marks_it_completed() {
+        let mut data = data();
+        data.shows.push(show("gig", "2025-01-20", &[]));
+        let complete = change(json!({
+            "change_type": "complete", "entity_type": "show", "entity_id": "gig"
+        }));
+        apply_change(&mut data, &complete, &[]).unwrap();
+        assert_eq!(data.shows[0].status, ShowStatus::Completed);
+    }
+
+    #[test]
+    fn invalid_statuses_are_rejected() {
+        let unknown = serde_json::from_value::<TimelineChange>(json!({
+            "change_type": "delay", "entity_type": "task", "entity_id": "mix",
+            "new_status": "sold_out"
+        }));
+        assert!(unknown.is_err_and(|e| e.to_string().starts_with("invalid task status")));
+        let released_task = serde_json::from_value::<TimelineChange>(json!({
+            "change_type": "delay", "entity_type": "task", "entity_id": "mix",
+            "new_status": "released"
+        }));
+        assert!(released_task.is_err());
+
+        let mut data = data();
+        let mut mismatched = change(json!({
+            "change_type": "delay", "entity_type": "task", "entity_id": "mix"
+        }));
+        mismatched.new_status = Some(EntityStatus::Show(ShowStatus::Active));
+        assert!(apply_change(&mut data, &mismatched, &[]).is_err());
+    }
+}
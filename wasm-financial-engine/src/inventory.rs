@@ -0,0 +1,259 @@
+use serde::{Deserialize, Serialize};
+
+use crate::money::{self, Currency, Money, RoundingMode};
+use crate::Show;
+
+/// One price level of a show's ticket inventory, e.g. GA, VIP or a seated section
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TicketTier {
+    pub name: String,
+    pub capacity: u32,
+    /// Price per ticket before fees; what the show grosses for each ticket sold
+    #[serde(deserialize_with = "money::deserialize_amount")]
+    pub face_value: f64,
+    /// Per-ticket fees the buyer pays on top of face value, not part of the gross
+    #[serde(default, deserialize_with = "money::deserialize_amount")]
+    pub fees: f64,
+    /// Paid tickets
+    #[serde(default)]
+    pub sold: u32,
+    /// Complimentary tickets: they attend but bring in nothing
+    #[serde(default)]
+    pub comps: u32,
+    /// Seats held back from sale (production, artist and label holds)
+    #[serde(default)]
+    pub holds: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TierMetrics {
+    pub name: String,
+    /// Shows carrying a tier of this name
+    pub shows: usize,
+    pub capacity: u32,
+    /// Capacity left for sale once comps and holds are taken out
+    pub sellable: u32,
+    pub sold: u32,
+    pub comps: u32,
+    pub holds: u32,
+    /// Sold share (0-100) of sellable capacity
+    pub sell_through: f64,
+    /// Gross per paid ticket
+    pub average_price: f64,
+    pub gross: f64,
+    /// Gross if every sellable ticket sold at face value
+    pub gross_potential: f64,
+    pub fees: f64,
+}
+
+impl TicketTier {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.face_value < 0.0 || self.fees < 0.0 {
+            return Err(format!(
+                "Tier '{}' cannot have a negative face value or fees",
+                self.name
+            ));
+        }
+        if self.sold as u64 + self.comps as u64 + self.holds as u64 > self.capacity as u64 {
+            return Err(format!(
+                "Tier '{}' has more tickets sold, comped and held than its capacity of {}",
+                self.name, self.capacity
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn sellable(&self) -> u32 {
+        self.capacity
+            .saturating_sub(self.comps.saturating_add(self.holds))
+    }
+
+    pub fn gross(&self, currency: Currency, mode: RoundingMode) -> Result<Money, String> {
+        Money::from_f64(self.face_value, currency, mode).times(self.sold as i64)
+    }
+
+    pub fn gross_potential(&self, currency: Currency, mode: RoundingMode) -> Result<Money, String> {
+        Money::from_f64(self.face_value, currency, mode).times(self.sellable() as i64)
+    }
+
+    fn fee_total(&self, currency: Currency, mode: RoundingMode) -> Result<Money, String> {
+        Money::from_f64(self.fees, currency, mode).times(self.sold as i64)
+    }
+}
+
+/// Sum of ticket counts, as an error rather than a wrap on overflow
+pub fn ticket_total(counts: impl IntoIterator<Item = u32>) -> Result<u32, String> {
+    counts
+        .into_iter()
+        .try_fold(0u32, |total, count| total.checked_add(count))
+        .ok_or_else(|| "Ticket count is too large".to_string())
+}
+
+/// Face value of every paid ticket across `tiers`
+pub fn gross(
+    tiers: &[TicketTier],
+    currency: Currency,
+    mode: RoundingMode,
+) -> Result<Money, String> {
+    tiers.iter().try_fold(Money::zero(currency), |total, tier| {
+        total.checked_add(&tier.gross(currency, mode)?)
+    })
+}
+
+/// Metrics per tier name across shows, in the order names first appear. Amounts must
+/// already be in `currency`.
+pub fn tier_metrics<'a>(
+    shows: impl IntoIterator<Item = &'a [TicketTier]>,
+    currency: Currency,
+    mode: RoundingMode,
+) -> Result<Vec<TierMetrics>, String> {
+    // (metrics, gross, potential, fees)
+    let mut groups: Vec<(TierMetrics, Money, Money, Money)> = Vec::new();
+    for tiers in shows {
+        for tier in tiers {
+            let position = groups.iter().position(|(m, ..)| m.name == tier.name);
+            let i = match position {
+                Some(i) => i,
+                None => {
+                    groups.push((
+                        TierMetrics {
+                            name: tier.name.clone(),
+                            shows: 0,
+                            capacity: 0,
+                            sellable: 0,
+                            sold: 0,
+                            comps: 0,
+                            holds: 0,
+                            sell_through: 0.0,
+                            average_price: 0.0,
+                            gross: 0.0,
+                            gross_potential: 0.0,
+                            fees: 0.0,
+                        },
+                        Money::zero(currency),
+                        Money::zero(currency),
+                        Money::zero(currency),
+                    ));
+                    groups.len() - 1
+                }
+            };
+            let (metrics, gross, potential, fees) = &mut groups[i];
+            metrics.shows += 1;
+            metrics.capacity = ticket_total([metrics.capacity, tier.capacity])?;
+            metrics.sellable = ticket_total([metrics.sellable, tier.sellable()])?;
+            metrics.sold = ticket_total([metrics.sold, tier.sold])?;
+            metrics.comps = ticket_total([metrics.comps, tier.comps])?;
+            metrics.holds = ticket_total([metrics.holds, tier.holds])?;
+            *gross = gross.checked_add(&tier.gross(currency, mode)?)?;
+            *potential = potential.checked_add(&tier.gross_potential(currency, mode)?)?;
+            *fees = fees.checked_add(&tier.fee_total(currency, mode)?)?;
+        }
+    }
+
+    Ok(groups
+        .into_iter()
+        .map(|(mut metrics, gross, potential, fees)| {
+            if metrics.sellable > 0 {
+                metrics.sell_through = metrics.sold as f64 / metrics.sellable as f64 * 100.0;
+            }
+            if metrics.sold > 0 {
+                metrics.average_price = gross.divide(metrics.sold as i64, mode).to_f64();
+            }
+            metrics.gross = gross.to_f64();
+            metrics.gross_potential = potential.to_f64();
+            metrics.fees = fees.to_f64();
+            metrics
+        })
+        .collect())
+}
+
+/// Take a tiered show's capacity and paid tickets from its tiers, and its gross box office
+/// from their face value unless one is given
+pub fn derive_totals(show: &mut Show, mode: RoundingMode) -> Result<(), String> {
+    if show.tiers.is_empty() {
+        return Ok(());
+    }
+    for tier in &show.tiers {
+        tier.validate()
+            .map_err(|e| format!("Show on {}: {}", show.date, e))?;
+    }
+    show.capacity = ticket_total(show.tiers.iter().map(|t| t.capacity))?;
+    show.tickets_sold = ticket_total(show.tiers.iter().map(|t| t.sold))?;
+    if show.gross_box_office.is_none() {
+        show.gross_box_office = Some(gross(&show.tiers, show.currency, mode)?.to_f64());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tier(name: &str, capacity: u32, face_value: f64, sold: u32) -> TicketTier {
+        TicketTier {
+            name: name.to_string(),
+            capacity,
+            face_value,
+            fees: 5.0,
+            sold,
+            comps: 10,
+            holds: 20,
+        }
+    }
+
+    fn show(tiers: Vec<TicketTier>) -> Show {
+        let mut show: Show = serde_json::from_value(json!({
+            "date": "2025-06-01",
+            "revenue": 0,
+            "expenses": 0,
+        }))
+        .unwrap();
+        show.tiers = tiers;
+        show
+    }
+
+    #[test]
+    fn tiers_set_show_totals_and_gross() {
+        let mut show = show(vec![tier("GA", 500, 40.0, 300), tier("VIP", 100, 90.0, 50)]);
+        derive_totals(&mut show, RoundingMode::default()).unwrap();
+        assert_eq!(show.capacity, 600);
+        assert_eq!(show.tickets_sold, 350);
+        assert_eq!(show.gross_box_office, Some(16500.0));
+    }
+
+    #[test]
+    fn oversold_tier_is_rejected() {
+        let mut show = show(vec![tier("GA", 100, 40.0, 80)]);
+        assert!(derive_totals(&mut show, RoundingMode::default()).is_err());
+    }
+
+    #[test]
+    fn ticket_totals_do_not_wrap() {
+        assert_eq!(ticket_total([1, 2, 3]), Ok(6));
+        assert!(ticket_total([u32::MAX, 1]).is_err());
+    }
+
+    #[test]
+    fn metrics_group_tiers_by_name() {
+        let first = [tier("GA", 500, 40.0, 300), tier("VIP", 100, 90.0, 50)];
+        let second = [tier("GA", 300, 50.0, 270)];
+        let metrics = tier_metrics(
+            [first.as_slice(), second.as_slice()],
+            Currency::default(),
+            RoundingMode::default(),
+        )
+        .unwrap();
+        assert_eq!(metrics.len(), 2);
+        let ga = &metrics[0];
+        assert_eq!(
+            (ga.shows, ga.capacity, ga.sellable, ga.sold),
+            (2, 800, 740, 570)
+        );
+        assert_eq!(ga.gross, 25500.0);
+        assert_eq!(ga.average_price, 44.74);
+        assert_eq!(ga.fees, 2850.0);
+        assert_eq!(ga.gross_potential, 32300.0);
+        assert_eq!(metrics[1].name, "VIP");
+    }
+}
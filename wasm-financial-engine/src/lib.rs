@@ -1,6 +1,77 @@
-use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use wasm_bindgen::prelude::*;
+
+mod calendar;
+mod commission;
+mod date;
+mod deal;
+mod demand;
+mod forecast;
+mod fx;
+mod history;
+mod inventory;
+mod money;
+mod monte_carlo;
+mod pace;
+mod pricing;
+mod schedule;
+mod sensitivity;
+mod settlement;
+mod status;
+mod tax;
+mod validation;
+
+pub use calendar::DelayUnit;
+use calendar::WorkingCalendar;
+use commission::CommissionInputs;
+pub use commission::{
+    CommissionAgreement, CommissionBasis, CommissionLine, CommissionParty, CommissionReport,
+    CommissionTier, IncomeLine, PartyCommission, ShowCommissions,
+};
+use deal::DealInputs;
+pub use deal::{DealBasis, DealPayout, DealStructure, DealTerms, SellThroughBonus};
+use demand::DemandInputs;
+pub use demand::{
+    DemandCurve, DemandOptions, ElasticityEstimate, ElasticitySource, MarketElasticity,
+};
+pub use forecast::backtest::{BacktestOptions, BacktestPoint, BacktestResult, HorizonStats};
+pub use forecast::{Granularity, ModelScore};
+pub use fx::FxRateTable;
+use fx::FxRates;
+use history::{History, Snapshot};
+pub use history::{HistoryEntry, HistoryState};
+pub use inventory::{TicketTier, TierMetrics};
+use money::DEFAULT_CURRENCY;
+pub use money::{Currency, Money, RoundingMode};
+pub use monte_carlo::{
+    Distribution, HistogramBin, ShowDistributions, SimulationOptions, SimulationResult,
+};
+pub use pace::{PaceOptions, PacePoint, PaceReport, PaceStatus, SalesCurve, SalesPoint, ShowPace};
+use pricing::PricingInputs;
+pub use pricing::{
+    PriceOptimizationOptions, PriceOptimizationReport, PricePoint, PriceTier, PricingPlan,
+    ShowPriceOptimization,
+};
+pub use schedule::leveling::{
+    LeveledTask, LevelingReport, MilestoneSlip, OverAllocation, Resource, ResourceKind,
+};
+pub use schedule::risk::{MilestoneRisk, RiskContributor, ScheduleRiskOptions, ScheduleRiskResult};
+use schedule::Graph;
+pub use schedule::{CascadeEntry, EntityKind, ScheduleAnalysis, ScheduleEntry};
+pub use sensitivity::{
+    Driver, SensitivityOptions, SensitivityResult, SpiderPoint, SpiderSeries, TornadoBar,
+};
+pub use settlement::{
+    Deduction, DeductionRule, LineItem, PromoterCost, SettlementInput, SettlementSheet, TierLine,
+    TierSales,
+};
+pub use status::{
+    ChangeType, EntityStatus, ReleaseStatus, ReleaseType, ShowStatus, TaskPriority, TaskStatus,
+};
+use tax::TaxRules;
+pub use tax::{CountryTaxRule, ShowTax, TaxSummary, WhtApplicationPoint};
+pub use validation::{Diagnostic, DiagnosticKind, Severity, ValidationReport};
 
 // Import the `console.log` function from the browser
 #[wasm_bindgen]
@@ -14,13 +85,80 @@ macro_rules! console_log {
     ($($t:tt)*) => (log(&format_args!($($t)*).to_string()))
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Show {
     pub date: String,
+    #[serde(default)]
+    pub currency: Currency,
+    /// Replaced by the payout for shows with `deal` terms, so those can leave it out
+    #[serde(default, deserialize_with = "money::deserialize_amount")]
     pub revenue: f64,
+    #[serde(deserialize_with = "money::deserialize_amount")]
     pub expenses: f64,
+    /// Taken from `tiers` when the show has them
+    #[serde(default)]
     pub capacity: u32,
+    /// Paid tickets; taken from `tiers` when the show has them
+    #[serde(default)]
     pub tickets_sold: u32,
+    /// Ticket inventory by price level, in the show's currency
+    #[serde(default)]
+    pub tiers: Vec<TicketTier>,
+    /// When present, `revenue` is derived from these terms instead of taken as given
+    #[serde(default)]
+    pub deal: Option<DealTerms>,
+    #[serde(default, deserialize_with = "money::deserialize_opt_amount")]
+    pub gross_box_office: Option<f64>,
+    #[serde(default, deserialize_with = "money::deserialize_amount")]
+    pub promoter_expenses: f64,
+    /// Part of `expenses` spent on travel and transport
+    #[serde(default, deserialize_with = "money::deserialize_amount")]
+    pub travel_expenses: f64,
+    /// Country code used to look up withholding tax and VAT rules
+    #[serde(default)]
+    pub country: Option<String>,
+    /// Market or region for demand elasticity; falls back to `country`
+    #[serde(default)]
+    pub market: Option<String>,
+    /// Treaty-reduced withholding applies (certificate of residence on file)
+    #[serde(default)]
+    pub treaty_relief: bool,
+    /// Income beyond the performance fee (merch, reimbursements), used for commissions
+    #[serde(default)]
+    pub other_income: Vec<IncomeLine>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ScenarioResult {
+    pub current_revenue: f64,
+    pub current_expenses: f64,
+    pub current_profit: f64,
+    pub projected_revenue: f64,
+    pub projected_expenses: f64,
+    pub projected_profit: f64,
+    pub profit_change_percent: f64,
+    pub new_ticket_price: f64,
+    pub projected_tickets: u32,
+    pub curve: DemandCurve,
+    pub elasticities: Vec<ElasticityEstimate>,
+    pub shows: Vec<ShowScenario>,
+}
+
+/// One show under a scenario, in the base currency
+#[derive(Serialize, Deserialize)]
+pub struct ShowScenario {
+    pub show_index: usize,
+    pub date: String,
+    pub market: Option<String>,
+    pub elasticity: f64,
+    /// Capacity after the scenario's capacity change; projected tickets never exceed it
+    pub capacity: u32,
+    pub current_tickets: u32,
+    pub projected_tickets: u32,
+    pub current_revenue: f64,
+    pub projected_revenue: f64,
+    pub projected_expenses: f64,
+    pub projected_profit: f64,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -29,10 +167,38 @@ pub struct FinancialMetrics {
     pub total_expenses: f64,
     pub net_profit: f64,
     pub profit_margin: f64,
+    /// Ticket gross per paid ticket, weighted across tiers; comps are not counted
     pub average_ticket_price: f64,
+    /// Paid tickets as a share (0-100) of capacity
     pub utilization_rate: f64,
+    /// Paid and comped tickets as a share (0-100) of capacity
+    pub attendance_rate: f64,
+    pub paid_attendance: u32,
+    pub comp_attendance: u32,
+    pub held_tickets: u32,
+    /// Ticket gross if every sellable ticket sold; shows without tiers count their capacity
+    /// at their own average price
+    pub gross_potential: f64,
     pub revenue_per_show: f64,
     pub break_even_tickets: f64,
+    pub base_currency: Currency,
+    pub currency_breakdown: Vec<CurrencyBreakdown>,
+    pub taxes: TaxSummary,
+    /// Inventory per tier name across the shows that have tiers
+    pub ticket_tiers: Vec<TierMetrics>,
+}
+
+/// Totals for the shows settled in one currency, in that currency and in the base currency
+#[derive(Serialize, Deserialize)]
+pub struct CurrencyBreakdown {
+    pub currency: Currency,
+    pub shows: usize,
+    pub revenue: f64,
+    pub expenses: f64,
+    pub net_profit: f64,
+    pub revenue_in_base: f64,
+    pub expenses_in_base: f64,
+    pub net_profit_in_base: f64,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -43,6 +209,22 @@ pub struct ForecastResult {
     pub confidence_interval: Vec<f64>,
     pub trend_slope: f64,
     pub seasonality_factor: f64,
+    pub periods: Vec<ForecastPeriod>,
+    pub model: String,
+    pub metrics: ModelScore,
+    pub candidates: Vec<ModelScore>,
+}
+
+/// One projected calendar period, labelled `YYYY-MM` (or `YYYY-Www` for weekly forecasts)
+#[derive(Serialize, Deserialize)]
+pub struct ForecastPeriod {
+    pub month: String,
+    pub revenue: f64,
+    pub expenses: f64,
+    pub profit: f64,
+    pub net: f64,
+    pub p50: f64,
+    pub p90: f64,
 }
 
 // Timeline Maestro v3.0 - Timeline Simulation Types
@@ -50,24 +232,42 @@ pub struct ForecastResult {
 pub struct TimelineTask {
     pub id: String,
     pub task_type: String,
-    pub status: String,
-    pub priority: String,
+    pub status: TaskStatus,
+    pub priority: TaskPriority,
     pub deadline: String,
     pub estimated_hours: f64,
+    /// Three-point estimate for schedule risk; any bound left out uses the most likely hours
+    #[serde(default)]
+    pub optimistic_hours: Option<f64>,
+    /// Defaults to `estimated_hours`
+    #[serde(default)]
+    pub most_likely_hours: Option<f64>,
+    #[serde(default)]
+    pub pessimistic_hours: Option<f64>,
     pub completion_percentage: f64,
+    #[serde(deserialize_with = "money::deserialize_amount")]
     pub cost_impact: f64,
+    #[serde(deserialize_with = "money::deserialize_amount")]
     pub revenue_impact: f64,
     pub dependencies: Vec<String>,
+    /// Ids of the resources working on the task
+    #[serde(default)]
+    pub assignees: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct TimelineRelease {
     pub id: String,
-    pub release_type: String,
+    pub release_type: ReleaseType,
+    #[serde(default)]
+    pub status: ReleaseStatus,
     pub release_date: String,
+    #[serde(deserialize_with = "money::deserialize_amount")]
     pub budget: f64,
+    #[serde(deserialize_with = "money::deserialize_amount")]
     pub projected_revenue: f64,
     pub platforms: Vec<String>,
+    #[serde(deserialize_with = "money::deserialize_amount")]
     pub marketing_spend: f64,
     pub dependencies: Vec<String>,
 }
@@ -76,28 +276,73 @@ pub struct TimelineRelease {
 pub struct TimelineShow {
     pub id: String,
     pub date: String,
+    #[serde(deserialize_with = "money::deserialize_amount")]
     pub revenue: f64,
+    #[serde(deserialize_with = "money::deserialize_amount")]
     pub expenses: f64,
-    pub status: String,
+    pub status: ShowStatus,
     pub venue_capacity: u32,
     pub expected_attendance: u32,
+    #[serde(default)]
+    pub dependencies: Vec<String>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct TimelineData {
     pub tasks: Vec<TimelineTask>,
     pub releases: Vec<TimelineRelease>,
     pub shows: Vec<TimelineShow>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(try_from = "RawTimelineChange")]
 pub struct TimelineChange {
-    pub change_type: String, // "delay", "complete", "reschedule", "cancel"
-    pub entity_type: String, // "task", "release", "show"
+    pub change_type: ChangeType,
+    pub entity_type: EntityKind,
     pub entity_id: String,
     pub new_date: Option<String>,
-    pub new_status: Option<String>,
+    /// A task, release or show status, matching `entity_type`
+    pub new_status: Option<EntityStatus>,
     pub new_completion: Option<f64>,
+    /// Move the entity this many days from its current date instead of giving `new_date`
+    pub delay_days: Option<i64>,
+    pub delay_unit: DelayUnit,
+}
+
+/// A change as it arrives, before `new_status` is checked against the entity type
+#[derive(Deserialize)]
+struct RawTimelineChange {
+    change_type: ChangeType,
+    entity_type: EntityKind,
+    entity_id: String,
+    new_date: Option<String>,
+    new_status: Option<String>,
+    new_completion: Option<f64>,
+    #[serde(default)]
+    delay_days: Option<i64>,
+    #[serde(default)]
+    delay_unit: DelayUnit,
+}
+
+impl TryFrom<RawTimelineChange> for TimelineChange {
+    type Error = String;
+
+    fn try_from(raw: RawTimelineChange) -> Result<Self, Self::Error> {
+        let new_status = raw
+            .new_status
+            .map(|value| EntityStatus::parse(raw.entity_type, &value))
+            .transpose()?;
+        Ok(TimelineChange {
+            change_type: raw.change_type,
+            entity_type: raw.entity_type,
+            entity_id: raw.entity_id,
+            new_date: raw.new_date,
+            new_status,
+            new_completion: raw.new_completion,
+            delay_days: raw.delay_days,
+            delay_unit: raw.delay_unit,
+        })
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -110,16 +355,63 @@ pub struct TimelineSimulationResult {
     pub revenue_change: f64,
     pub expense_change: f64,
     pub critical_path: Vec<String>,
+    pub cascade: Vec<CascadeEntry>,
+}
+
+/// Impact of one change in a batch, given the changes before it have been applied
+#[derive(Serialize, Deserialize)]
+pub struct MarginalImpact {
+    pub change: TimelineChange,
+    pub result: TimelineSimulationResult,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BatchSimulationResult {
+    pub combined: TimelineSimulationResult,
+    pub marginal: Vec<MarginalImpact>,
+}
+
+/// A committed change and the timeline version it produced
+#[derive(Serialize, Deserialize)]
+pub struct AppliedChange {
+    pub version: u32,
+    pub result: TimelineSimulationResult,
 }
 
 #[wasm_bindgen]
 pub struct FinancialEngine {
     shows: Vec<Show>,
+    rounding: RoundingMode,
+    base_currency: Currency,
+    fx_rates: FxRates,
+    tax_rules: TaxRules,
+    commissions: Vec<CommissionAgreement>,
+    demand: DemandOptions,
 }
 
 #[wasm_bindgen]
 pub struct TimelineSimulator {
     timeline_data: Option<TimelineData>,
+    rounding: RoundingMode,
+    /// Date time-relative metrics are measured from; `None` uses the host clock
+    as_of: Option<date::Date>,
+    calendar: WorkingCalendar,
+    resources: Vec<Resource>,
+    version: u32,
+    last_change: Option<TimelineChange>,
+    history: History,
+}
+
+impl Default for FinancialEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for TimelineSimulator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[wasm_bindgen]
@@ -129,14 +421,22 @@ impl FinancialEngine {
         console_log!("🚀 WASM Financial Engine initialized");
         FinancialEngine {
             shows: Vec::new(),
+            rounding: RoundingMode::default(),
+            base_currency: DEFAULT_CURRENCY,
+            fx_rates: FxRates::default(),
+            tax_rules: TaxRules::default(),
+            commissions: Vec::new(),
+            demand: DemandOptions::default(),
         }
     }
 
     /// Add show data to the engine
     #[wasm_bindgen]
     pub fn add_show(&mut self, show_json: &str) -> Result<(), JsValue> {
-        let show: Show = serde_json::from_str(show_json)
+        let mut show: Show = serde_json::from_str(show_json)
             .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+        self.prepare_show(&mut show)
+            .map_err(|e| JsValue::from_str(&e))?;
         self.shows.push(show);
         Ok(())
     }
@@ -144,13 +444,94 @@ impl FinancialEngine {
     /// Load multiple shows from JSON array
     #[wasm_bindgen]
     pub fn load_shows(&mut self, shows_json: &str) -> Result<(), JsValue> {
-        let shows: Vec<Show> = serde_json::from_str(shows_json)
+        let mut shows: Vec<Show> = serde_json::from_str(shows_json)
             .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+        for show in &mut shows {
+            self.prepare_show(show).map_err(|e| JsValue::from_str(&e))?;
+        }
         self.shows = shows;
         console_log!("📊 Loaded {} shows into WASM engine", self.shows.len());
         Ok(())
     }
 
+    /// Choose how amounts are rounded to cents: `"half_even"` (banker's, default) or `"half_up"`
+    #[wasm_bindgen]
+    pub fn set_rounding_mode(&mut self, mode: &str) -> Result<(), JsValue> {
+        self.rounding = RoundingMode::parse(mode).map_err(|e| JsValue::from_str(&e))?;
+        Ok(())
+    }
+
+    /// Currency all aggregates are reported in (default EUR)
+    #[wasm_bindgen]
+    pub fn set_base_currency(&mut self, currency: &str) -> Result<(), JsValue> {
+        self.base_currency = Currency::parse(currency).map_err(|e| JsValue::from_str(&e))?;
+        Ok(())
+    }
+
+    /// Load dated FX tables: `[{ "date": "2025-01", "base": "EUR", "rates": { "USD": 1.09 } }]`
+    #[wasm_bindgen]
+    pub fn load_fx_rates(&mut self, rates_json: &str) -> Result<(), JsValue> {
+        let tables: Vec<FxRateTable> = serde_json::from_str(rates_json)
+            .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+        self.fx_rates = FxRates::from_tables(tables).map_err(|e| JsValue::from_str(&e))?;
+        console_log!(
+            "💱 Loaded {} FX rate tables into WASM engine",
+            self.fx_rates.len()
+        );
+        Ok(())
+    }
+
+    /// Load per-country tax rules: `[{ "country": "ES", "wht_rate": 24, "treaty_rate": 15, "vat_rate": 21 }]`
+    #[wasm_bindgen]
+    pub fn load_tax_rules(&mut self, rules_json: &str) -> Result<(), JsValue> {
+        let rules: Vec<CountryTaxRule> = serde_json::from_str(rules_json)
+            .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+        self.tax_rules = TaxRules::from_rules(rules).map_err(|e| JsValue::from_str(&e))?;
+        console_log!(
+            "🧾 Loaded {} country tax rules into WASM engine",
+            self.tax_rules.len()
+        );
+        Ok(())
+    }
+
+    /// Load commission agreements: `[{ "party": "agent", "rate": 10, "basis": "gross", "tiers": [{ "above": 20000, "rate": 12 }] }]`
+    #[wasm_bindgen]
+    pub fn load_commission_agreements(&mut self, agreements_json: &str) -> Result<(), JsValue> {
+        let agreements: Vec<CommissionAgreement> = serde_json::from_str(agreements_json)
+            .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+        for agreement in &agreements {
+            agreement.validate().map_err(|e| JsValue::from_str(&e))?;
+        }
+        self.commissions = agreements;
+        console_log!(
+            "🤝 Loaded {} commission agreements into WASM engine",
+            self.commissions.len()
+        );
+        Ok(())
+    }
+
+    /// Commission breakdown per show and per party, in the base currency
+    #[wasm_bindgen]
+    pub fn calculate_commissions(&self) -> Result<String, JsValue> {
+        if self.shows.is_empty() {
+            return Err(JsValue::from_str("No shows loaded"));
+        }
+
+        let converted = self.resolved_shows()?;
+        let inputs = Self::commission_inputs(&converted);
+
+        let report = commission::calculate(
+            &self.commissions,
+            &inputs,
+            self.base_currency,
+            self.rounding,
+        )
+        .map_err(|e| JsValue::from_str(&format!("Commission error: {}", e)))?;
+
+        serde_json::to_string(&report)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
     /// Calculate comprehensive financial metrics
     #[wasm_bindgen]
     pub fn calculate_metrics(&self) -> Result<String, JsValue> {
@@ -158,200 +539,439 @@ impl FinancialEngine {
             return Err(JsValue::from_str("No shows loaded"));
         }
 
-        let total_revenue: f64 = self.shows.iter().map(|s| s.revenue).sum::<f64>();
-        let total_expenses: f64 = self.shows.iter().map(|s| s.expenses).sum::<f64>();
-        let net_profit = total_revenue - total_expenses;
-        let profit_margin = if total_revenue > 0.0 {
-            (net_profit / total_revenue) * 100.0
-        } else {
-            0.0
-        };
+        let mode = self.rounding;
+        let base = self.base_currency;
+        let to_js = |e: String| JsValue::from_str(&e);
+        let converted = self.resolved_shows()?;
+
+        let mut total_revenue = Money::zero(base);
+        let mut total_expenses = Money::zero(base);
+        // currency -> (shows, revenue, expenses, revenue in base, expenses in base)
+        let mut by_currency: BTreeMap<Currency, (usize, Money, Money, Money, Money)> =
+            BTreeMap::new();
+        for (show, in_base) in self.shows.iter().zip(&converted) {
+            let revenue_in_base = Money::from_f64(in_base.revenue, base, mode);
+            let expenses_in_base = Money::from_f64(in_base.expenses, base, mode);
+            total_revenue = total_revenue.checked_add(&revenue_in_base).map_err(to_js)?;
+            total_expenses = total_expenses
+                .checked_add(&expenses_in_base)
+                .map_err(to_js)?;
+
+            let entry = by_currency.entry(show.currency).or_insert((
+                0,
+                Money::zero(show.currency),
+                Money::zero(show.currency),
+                Money::zero(base),
+                Money::zero(base),
+            ));
+            entry.0 += 1;
+            let revenue = match self.deal_payout(show).map_err(to_js)? {
+                Some(payout) => payout.payout,
+                None => show.revenue,
+            };
+            entry.1 = entry
+                .1
+                .checked_add(&Money::from_f64(revenue, show.currency, mode))
+                .map_err(to_js)?;
+            entry.2 = entry
+                .2
+                .checked_add(&Money::from_f64(show.expenses, show.currency, mode))
+                .map_err(to_js)?;
+            entry.3 = entry.3.checked_add(&revenue_in_base).map_err(to_js)?;
+            entry.4 = entry.4.checked_add(&expenses_in_base).map_err(to_js)?;
+        }
 
-        let total_tickets: u32 = self.shows.iter().map(|s| s.tickets_sold).sum::<u32>();
-        let total_capacity: u32 = self.shows.iter().map(|s| s.capacity).sum::<u32>();
+        let mut show_taxes = Vec::with_capacity(converted.len());
+        for show in &converted {
+            show_taxes.push(
+                self.tax_rules
+                    .show_tax(
+                        show.country.as_deref(),
+                        show.treaty_relief,
+                        Money::from_f64(show.revenue, base, mode),
+                        Money::from_f64(show.expenses, base, mode),
+                        mode,
+                    )
+                    .map_err(to_js)?,
+            );
+        }
+        let taxes = TaxSummary::from_shows(show_taxes, base, mode);
+
+        let mut currency_breakdown = Vec::with_capacity(by_currency.len());
+        for (currency, (shows, revenue, expenses, revenue_in_base, expenses_in_base)) in by_currency
+        {
+            currency_breakdown.push(CurrencyBreakdown {
+                currency,
+                shows,
+                revenue: revenue.to_f64(),
+                expenses: expenses.to_f64(),
+                net_profit: revenue.checked_sub(&expenses).map_err(to_js)?.to_f64(),
+                revenue_in_base: revenue_in_base.to_f64(),
+                expenses_in_base: expenses_in_base.to_f64(),
+                net_profit_in_base: revenue_in_base
+                    .checked_sub(&expenses_in_base)
+                    .map_err(to_js)?
+                    .to_f64(),
+            });
+        }
 
-        let average_ticket_price = if total_tickets > 0 {
-            total_revenue / total_tickets as f64
+        let net_profit = total_revenue.checked_sub(&total_expenses).map_err(to_js)?;
+        let profit_margin = if total_revenue.minor_units() > 0 {
+            (net_profit.minor_units() as f64 / total_revenue.minor_units() as f64) * 100.0
         } else {
             0.0
         };
 
-        let utilization_rate = if total_capacity > 0 {
-            (total_tickets as f64 / total_capacity as f64) * 100.0
-        } else {
-            0.0
-        };
+        let total_tickets =
+            inventory::ticket_total(self.shows.iter().map(|s| s.tickets_sold)).map_err(to_js)?;
+        let total_capacity =
+            inventory::ticket_total(self.shows.iter().map(|s| s.capacity)).map_err(to_js)?;
+        let comps =
+            inventory::ticket_total(self.shows.iter().flat_map(|s| &s.tiers).map(|t| t.comps))
+                .map_err(to_js)?;
+        let holds =
+            inventory::ticket_total(self.shows.iter().flat_map(|s| &s.tiers).map(|t| t.holds))
+                .map_err(to_js)?;
+        let attendance = inventory::ticket_total([total_tickets, comps]).map_err(to_js)?;
+
+        // Tiered shows gross their tickets' face value; others their gross box office, or
+        // their revenue without one (a deal show's revenue is the artist payout)
+        let mut ticket_gross = Money::zero(base);
+        let mut gross_potential = Money::zero(base);
+        for show in &converted {
+            if show.tiers.is_empty() {
+                let gross =
+                    Money::from_f64(show.gross_box_office.unwrap_or(show.revenue), base, mode);
+                ticket_gross = ticket_gross.checked_add(&gross).map_err(to_js)?;
+                if show.tickets_sold > 0 {
+                    let potential = gross
+                        .divide(show.tickets_sold as i64, mode)
+                        .times(show.capacity as i64)
+                        .map_err(to_js)?;
+                    gross_potential = gross_potential.checked_add(&potential).map_err(to_js)?;
+                }
+            } else {
+                ticket_gross = ticket_gross
+                    .checked_add(&inventory::gross(&show.tiers, base, mode).map_err(to_js)?)
+                    .map_err(to_js)?;
+                for tier in &show.tiers {
+                    gross_potential = gross_potential
+                        .checked_add(&tier.gross_potential(base, mode).map_err(to_js)?)
+                        .map_err(to_js)?;
+                }
+            }
+        }
+        let ticket_tiers =
+            inventory::tier_metrics(converted.iter().map(|s| s.tiers.as_slice()), base, mode)
+                .map_err(to_js)?;
 
-        let revenue_per_show = if !self.shows.is_empty() {
-            total_revenue / self.shows.len() as f64
+        let average_ticket_price = ticket_gross.divide(total_tickets as i64, mode);
+
+        let (utilization_rate, attendance_rate) = if total_capacity > 0 {
+            (
+                (total_tickets as f64 / total_capacity as f64) * 100.0,
+                (attendance as f64 / total_capacity as f64) * 100.0,
+            )
         } else {
-            0.0
+            (0.0, 0.0)
         };
 
+        let revenue_per_show = total_revenue.divide(self.shows.len() as i64, mode);
+
         // Calculate break-even point per show
-        let avg_expenses_per_show = if !self.shows.is_empty() {
-            total_expenses / self.shows.len() as f64
-        } else {
-            0.0
-        };
+        let avg_expenses_per_show = total_expenses.divide(self.shows.len() as i64, mode);
 
-        let break_even_tickets = if average_ticket_price > 0.0 {
-            avg_expenses_per_show / average_ticket_price
+        let break_even_tickets = if average_ticket_price.minor_units() > 0 {
+            avg_expenses_per_show.minor_units() as f64 / average_ticket_price.minor_units() as f64
         } else {
             0.0
         };
 
         let metrics = FinancialMetrics {
-            total_revenue,
-            total_expenses,
-            net_profit,
+            total_revenue: total_revenue.to_f64(),
+            total_expenses: total_expenses.to_f64(),
+            net_profit: net_profit.to_f64(),
             profit_margin,
-            average_ticket_price,
+            average_ticket_price: average_ticket_price.to_f64(),
             utilization_rate,
-            revenue_per_show,
+            attendance_rate,
+            paid_attendance: total_tickets,
+            comp_attendance: comps,
+            held_tickets: holds,
+            gross_potential: gross_potential.to_f64(),
+            revenue_per_show: revenue_per_show.to_f64(),
             break_even_tickets,
+            base_currency: base,
+            currency_breakdown,
+            taxes,
+            ticket_tiers,
         };
 
         serde_json::to_string(&metrics)
             .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
     }
 
-    /// Advanced forecasting using linear regression and seasonality analysis
+    /// Per-show artist payout breakdown for shows with deal terms (in each show's currency)
+    #[wasm_bindgen]
+    pub fn calculate_deal_payouts(&self) -> Result<String, JsValue> {
+        #[derive(Serialize)]
+        struct ShowDealPayout<'a> {
+            show_index: usize,
+            date: &'a str,
+            currency: Currency,
+            #[serde(flatten)]
+            payout: DealPayout,
+        }
+
+        let mut payouts = Vec::new();
+        for (show_index, show) in self.shows.iter().enumerate() {
+            if let Some(payout) = self.deal_payout(show).map_err(|e| JsValue::from_str(&e))? {
+                payouts.push(ShowDealPayout {
+                    show_index,
+                    date: &show.date,
+                    currency: show.currency,
+                    payout,
+                });
+            }
+        }
+
+        serde_json::to_string(&payouts)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Build an itemized settlement sheet from box office figures and deal terms.
+    /// With `show_index` set, the currency, capacity and deal default to that loaded show.
+    #[wasm_bindgen]
+    pub fn settle_show(&self, settlement_json: &str) -> Result<String, JsValue> {
+        let input: SettlementInput = serde_json::from_str(settlement_json)
+            .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+
+        let show = match input.show_index {
+            Some(i) => Some(
+                self.shows
+                    .get(i)
+                    .ok_or_else(|| JsValue::from_str(&format!("Show index {} out of range", i)))?,
+            ),
+            None => None,
+        };
+        let deal = input
+            .deal
+            .as_ref()
+            .or_else(|| show.and_then(|s| s.deal.as_ref()))
+            .ok_or_else(|| JsValue::from_str("Settlement needs deal terms"))?;
+        let currency = input
+            .currency
+            .or_else(|| show.map(|s| s.currency))
+            .unwrap_or(self.base_currency);
+        let capacity = input
+            .capacity
+            .or_else(|| show.map(|s| s.capacity))
+            .unwrap_or(0);
+
+        let sheet = settlement::settle(&input, deal, currency, capacity, self.rounding)
+            .map_err(|e| JsValue::from_str(&format!("Settlement error: {}", e)))?;
+
+        serde_json::to_string(&sheet)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Monthly revenue forecast from the shows' calendar dates (trend + seasonality)
     #[wasm_bindgen]
     pub fn forecast_revenue(&self, months_ahead: u32) -> Result<String, JsValue> {
+        self.forecast(months_ahead, "month")
+    }
+
+    /// Revenue forecast bucketed by calendar `"month"` or `"week"`, auto-selecting the model
+    #[wasm_bindgen]
+    pub fn forecast(&self, periods_ahead: u32, granularity: &str) -> Result<String, JsValue> {
+        self.forecast_with_model(periods_ahead, granularity, "auto")
+    }
+
+    /// Revenue forecast with an explicit model: `auto`, `linear_seasonal`, `holt_winters`,
+    /// `moving_average` or `damped_trend`
+    #[wasm_bindgen]
+    pub fn forecast_with_model(
+        &self,
+        periods_ahead: u32,
+        granularity: &str,
+        model: &str,
+    ) -> Result<String, JsValue> {
         if self.shows.len() < 3 {
             return Err(JsValue::from_str("Need at least 3 shows for forecasting"));
         }
 
-        // Simple linear regression for trend analysis
-        let n = self.shows.len() as f64;
-        let sum_x: f64 = (0..self.shows.len()).map(|i| i as f64).sum::<f64>();
-        let sum_y: f64 = self.shows.iter().map(|s| s.revenue).sum::<f64>();
-        let sum_xy: f64 = self.shows.iter().enumerate()
-            .map(|(i, s)| i as f64 * s.revenue).sum::<f64>();
-        let sum_x2: f64 = (0..self.shows.len()).map(|i| (i as f64).powi(2)).sum::<f64>();
-
-        let trend_slope = (n * sum_xy - sum_x * sum_y) / (n * sum_x2 - sum_x.powi(2));
-        let intercept = (sum_y - trend_slope * sum_x) / n;
-
-        // Calculate seasonality factor (simplified)
-        let avg_revenue = sum_y / n;
-        let variance: f64 = self.shows.iter()
-            .map(|s| (s.revenue - avg_revenue).powi(2))
-            .sum::<f64>() / n;
-        let seasonality_factor = (variance.sqrt() / avg_revenue) * 100.0;
-
-        // Generate forecasts
-        let mut projected_revenue = Vec::new();
-        let mut projected_expenses = Vec::new();
-        let mut projected_profit = Vec::new();
-        let mut confidence_interval = Vec::new();
-
-        let total_expenses_forecast: f64 = self.shows.iter().map(|s| s.expenses).sum::<f64>();
-        let avg_expense_ratio = if sum_y > 0.0 {
-            total_expenses_forecast / sum_y
-        } else {
-            0.7 // Default 70% expense ratio
-        };
-
-        for i in 0..months_ahead {
-            let x = self.shows.len() as f64 + i as f64;
-            let base_revenue = intercept + trend_slope * x;
-            
-            // Add seasonality (simplified sine wave)
-            let seasonal_adjustment = 1.0 + (seasonality_factor / 100.0) * 
-                ((2.0 * std::f64::consts::PI * i as f64 / 12.0).sin());
-            
-            let revenue = base_revenue * seasonal_adjustment;
-            let expenses = revenue * avg_expense_ratio;
-            let profit = revenue - expenses;
-            
-            // Confidence interval (±15% for simplicity)
-            let confidence = revenue * 0.15;
-            
-            projected_revenue.push(revenue.max(0.0));
-            projected_expenses.push(expenses.max(0.0));
-            projected_profit.push(profit);
-            confidence_interval.push(confidence);
-        }
-
-        let forecast = ForecastResult {
-            projected_revenue,
-            projected_expenses,
-            projected_profit,
-            confidence_interval,
-            trend_slope,
-            seasonality_factor,
-        };
+        let granularity = Granularity::parse(granularity).map_err(|e| JsValue::from_str(&e))?;
+        let shows = self.resolved_shows()?;
+        let forecast = forecast::forecast(&shows, periods_ahead, granularity, model)
+            .map_err(|e| JsValue::from_str(&format!("Forecast error: {}", e)))?;
 
         serde_json::to_string(&forecast)
             .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
     }
 
+    /// Rolling-origin backtest of the revenue forecast against the loaded history
+    #[wasm_bindgen]
+    pub fn backtest_forecast(&self, options_json: &str) -> Result<String, JsValue> {
+        let options: BacktestOptions = serde_json::from_str(options_json)
+            .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+
+        if self.shows.len() <= options.min_train_shows {
+            return Err(JsValue::from_str(
+                "Need more shows than min_train_shows to backtest",
+            ));
+        }
+
+        let shows = self.resolved_shows()?;
+        let result = forecast::backtest::backtest(&shows, &options)
+            .map_err(|e| JsValue::from_str(&format!("Backtest error: {}", e)))?;
+
+        serde_json::to_string(&result)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Choose the demand curve and elasticities `scenario_analysis` projects ticket sales with:
+    /// `{ "curve": "logistic", "default_elasticity": -0.5, "markets": [{ "market": "DE", "elasticity": -0.8 }], "estimate_from_history": true }`
+    #[wasm_bindgen]
+    pub fn set_demand_model(&mut self, options_json: &str) -> Result<(), JsValue> {
+        let options: DemandOptions = serde_json::from_str(options_json)
+            .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+        options.validate().map_err(|e| JsValue::from_str(&e))?;
+        self.demand = options;
+        Ok(())
+    }
+
     /// Calculate profitability analysis for different scenarios
     #[wasm_bindgen]
-    pub fn scenario_analysis(&self, ticket_price_change: f64, capacity_change: f64, expense_change: f64) -> Result<String, JsValue> {
+    pub fn scenario_analysis(
+        &self,
+        ticket_price_change: f64,
+        capacity_change: f64,
+        expense_change: f64,
+    ) -> Result<String, JsValue> {
         if self.shows.is_empty() {
             return Err(JsValue::from_str("No shows loaded"));
         }
 
-        // Calculate current totals
-        let current_revenue: f64 = self.shows.iter().map(|s| s.revenue).sum::<f64>();
-        let current_expenses: f64 = self.shows.iter().map(|s| s.expenses).sum::<f64>();
-        let current_tickets: u32 = self.shows.iter().map(|s| s.tickets_sold).sum::<u32>();
-        
-        // Calculate average ticket price
-        let avg_ticket_price = if current_tickets > 0 {
-            current_revenue / current_tickets as f64
-        } else {
-            50.0 // Default
-        };
+        let shows = self.resolved_shows()?;
+        let result =
+            self.project_scenario(&shows, ticket_price_change, capacity_change, expense_change)?;
 
-        // Apply scenario changes
-        let new_ticket_price = avg_ticket_price * (1.0 + ticket_price_change / 100.0);
-        let new_capacity_multiplier = 1.0 + capacity_change / 100.0;
-        let new_expense_multiplier = 1.0 + expense_change / 100.0;
-
-        // Estimate demand elasticity (simple model: -0.5 elasticity)
-        let demand_change = ticket_price_change * -0.5;
-        let new_demand_multiplier = 1.0 + demand_change / 100.0;
-
-        let projected_tickets = (current_tickets as f64 * new_demand_multiplier * new_capacity_multiplier) as u32;
-        let projected_revenue = projected_tickets as f64 * new_ticket_price;
-        let projected_expenses = current_expenses * new_expense_multiplier;
-        let projected_profit = projected_revenue - projected_expenses;
-
-        let current_profit = current_revenue - current_expenses;
-        let profit_change = if current_profit != 0.0 {
-            ((projected_profit - current_profit) / current_profit.abs()) * 100.0
+        serde_json::to_string(&result)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Search each show's ticket price, and optionally up to `max_tiers` price tiers, for the most profit
+    /// under the demand model: `{ "min_sell_through": 80, "price_ceiling": 120, "accessible_price": 35, "accessible_share": 20, "max_tiers": 3 }`
+    #[wasm_bindgen]
+    pub fn optimize_ticket_prices(&self, options_json: &str) -> Result<String, JsValue> {
+        if self.shows.is_empty() {
+            return Err(JsValue::from_str("No shows loaded"));
+        }
+
+        let options: PriceOptimizationOptions = serde_json::from_str(options_json)
+            .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+        let shows = self.resolved_shows()?;
+        let demand_inputs = self.demand_inputs(&shows);
+        let elasticities = self.demand.elasticities(&demand_inputs);
+        let candidates: Vec<usize> = if options.show_indexes.is_empty() {
+            (0..shows.len()).collect()
         } else {
-            if projected_profit > 0.0 { 100.0 } else { -100.0 }
+            options.show_indexes.clone()
         };
+        let mut inputs = Vec::with_capacity(candidates.len());
+        for index in candidates {
+            let (show, demand) =
+                shows
+                    .get(index)
+                    .zip(demand_inputs.get(index))
+                    .ok_or_else(|| {
+                        JsValue::from_str(&format!(
+                            "Optimization error: no show at index {}",
+                            index
+                        ))
+                    })?;
+            inputs.push(PricingInputs {
+                show_index: index,
+                date: &show.date,
+                revenue: show.revenue,
+                expenses: show.expenses,
+                deal: show.deal.as_ref(),
+                promoter_expenses: show.promoter_expenses,
+                elasticity: demand::elasticity_for(&elasticities, demand.market),
+                demand: *demand,
+            });
+        }
 
-        #[derive(Serialize)]
-        struct ScenarioResult {
-            current_revenue: f64,
-            current_expenses: f64,
-            current_profit: f64,
-            projected_revenue: f64,
-            projected_expenses: f64,
-            projected_profit: f64,
-            profit_change_percent: f64,
-            new_ticket_price: f64,
-            projected_tickets: u32,
-        }
-
-        let result = ScenarioResult {
-            current_revenue,
-            current_expenses,
-            current_profit,
-            projected_revenue,
-            projected_expenses,
-            projected_profit,
-            profit_change_percent: profit_change,
-            new_ticket_price,
-            projected_tickets,
-        };
+        let report = pricing::optimize(
+            &inputs,
+            &options,
+            self.demand.curve,
+            self.base_currency,
+            self.rounding,
+        )
+        .map_err(|e| JsValue::from_str(&format!("Optimization error: {}", e)))?;
+
+        serde_json::to_string(&report)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Track on-sale shows' cumulative sales against past comparable shows and predict final attendance and sell-out:
+    /// `{ "shows": [{ "show_index": 4, "sales": [{ "date": "2025-03-01", "tickets": 120 }] }], "comparables": [...], "behind_threshold": 10 }`
+    #[wasm_bindgen]
+    pub fn track_sales_pace(&self, pace_json: &str) -> Result<String, JsValue> {
+        let options: PaceOptions = serde_json::from_str(pace_json)
+            .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+        if options.comparables.is_empty() {
+            return Err(JsValue::from_str(
+                "Sales pace needs at least one comparable show",
+            ));
+        }
+
+        let report = pace::track(&self.shows, &options)
+            .map_err(|e| JsValue::from_str(&format!("Pace error: {}", e)))?;
+
+        serde_json::to_string(&report)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Tornado and spider-plot data: net profit after withholding tax and commissions as each driver
+    /// moves on its own across the range: `{ "range": 20, "steps": 9, "drivers": ["ticket_price", "fx_rate"] }`
+    #[wasm_bindgen]
+    pub fn sensitivity_analysis(&self, options_json: &str) -> Result<String, JsValue> {
+        if self.shows.is_empty() {
+            return Err(JsValue::from_str("No shows loaded"));
+        }
+
+        let options: SensitivityOptions = serde_json::from_str(options_json)
+            .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+        let shows = self.resolved_shows()?;
+        let result = sensitivity::analyze(
+            &options,
+            self.base_currency,
+            self.rounding,
+            |driver, change| self.sensitivity_profit(&shows, driver, change),
+        )
+        .map_err(|e| JsValue::from_str(&format!("Sensitivity error: {}", e)))?;
+
+        serde_json::to_string(&result)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Run a seeded Monte Carlo simulation of net profit across the loaded shows
+    #[wasm_bindgen]
+    pub fn simulate_scenarios(&self, options_json: &str) -> Result<String, JsValue> {
+        if self.shows.is_empty() {
+            return Err(JsValue::from_str("No shows loaded"));
+        }
+
+        let options: SimulationOptions = serde_json::from_str(options_json)
+            .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+
+        let started = js_sys::Date::now();
+        let shows = self.resolved_shows()?;
+        let mut result = monte_carlo::simulate(&shows, &options)
+            .map_err(|e| JsValue::from_str(&format!("Simulation error: {}", e)))?;
+        result.time = js_sys::Date::now() - started;
 
         serde_json::to_string(&result)
             .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
@@ -360,7 +980,10 @@ impl FinancialEngine {
     /// Get engine statistics
     #[wasm_bindgen]
     pub fn get_stats(&self) -> String {
-        format!("{{\"shows_loaded\": {}, \"engine_version\": \"1.0.0\"}}", self.shows.len())
+        format!(
+            "{{\"shows_loaded\": {}, \"engine_version\": \"1.0.0\"}}",
+            self.shows.len()
+        )
     }
 }
 
@@ -372,60 +995,272 @@ impl TimelineSimulator {
         console_log!("🎯 Timeline Simulator initialized");
         TimelineSimulator {
             timeline_data: None,
+            rounding: RoundingMode::default(),
+            as_of: None,
+            calendar: WorkingCalendar::default(),
+            resources: Vec::new(),
+            version: 0,
+            last_change: None,
+            history: History::default(),
         }
     }
 
     /// Load timeline data into the simulator
     #[wasm_bindgen]
     pub fn load_timeline_data(&mut self, data_json: &str) -> Result<(), JsValue> {
-        let timeline_data: TimelineData = serde_json::from_str(data_json)
+        let timeline_data = Self::parse_timeline_data(data_json)
             .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
-        
-        console_log!("📊 Timeline data loaded: {} tasks, {} releases, {} shows", 
-            timeline_data.tasks.len(), 
-            timeline_data.releases.len(), 
-            timeline_data.shows.len());
-        
+
+        let report = validation::validate(&timeline_data);
+        if !report.valid {
+            let messages: Vec<&str> = report
+                .diagnostics
+                .iter()
+                .filter(|d| d.severity == Severity::Error)
+                .map(|d| d.message.as_str())
+                .collect();
+            return Err(JsValue::from_str(&format!(
+                "Validation error: {}",
+                messages.join("; ")
+            )));
+        }
+        for warning in &report.diagnostics {
+            console_log!("⚠️ {}", warning.message);
+        }
+
+        console_log!(
+            "📊 Timeline data loaded: {} tasks, {} releases, {} shows",
+            timeline_data.tasks.len(),
+            timeline_data.releases.len(),
+            timeline_data.shows.len()
+        );
+
         self.timeline_data = Some(timeline_data);
+        self.version = 0;
+        self.last_change = None;
+        self.history = History::default();
+        Ok(())
+    }
+
+    /// Current timeline, including committed changes, as `TimelineData` JSON
+    #[wasm_bindgen]
+    pub fn export_timeline_data(&self) -> Result<String, JsValue> {
+        let timeline_data = self
+            .timeline_data
+            .as_ref()
+            .ok_or_else(|| JsValue::from_str("Timeline data not loaded"))?;
+
+        serde_json::to_string(timeline_data)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Measure overdue, days remaining and schedules from this `YYYY-MM-DD` date instead of
+    /// today; an empty string goes back to the host clock
+    #[wasm_bindgen]
+    pub fn set_as_of(&mut self, as_of: &str) -> Result<(), JsValue> {
+        self.as_of = if as_of.trim().is_empty() {
+            None
+        } else {
+            Some(date::Date::parse(as_of).map_err(|e| JsValue::from_str(&e))?)
+        };
+        Ok(())
+    }
+
+    /// Load holidays (`YYYY-MM-DD` strings) skipped, along with weekends, by working-day delays
+    #[wasm_bindgen]
+    pub fn load_holiday_calendar(&mut self, holidays_json: &str) -> Result<(), JsValue> {
+        let holidays: Vec<String> = serde_json::from_str(holidays_json)
+            .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+
+        self.calendar =
+            WorkingCalendar::from_holidays(&holidays).map_err(|e| JsValue::from_str(&e))?;
+
+        console_log!(
+            "📅 Holiday calendar loaded: {} holidays",
+            self.calendar.len()
+        );
+        Ok(())
+    }
+
+    /// Load the people and crews tasks can be assigned to, with their weekly capacity
+    #[wasm_bindgen]
+    pub fn load_resources(&mut self, resources_json: &str) -> Result<(), JsValue> {
+        let resources: Vec<Resource> = serde_json::from_str(resources_json)
+            .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+
+        schedule::leveling::validate_resources(&resources)
+            .map_err(|e| JsValue::from_str(&format!("Validation error: {}", e)))?;
+
+        console_log!("👥 Resources loaded: {}", resources.len());
+        self.resources = resources;
+        Ok(())
+    }
+
+    /// Check timeline data for cycles, dangling, duplicate and self dependencies without loading it
+    #[wasm_bindgen]
+    pub fn validate_timeline_data(&self, data_json: &str) -> Result<String, JsValue> {
+        let timeline_data = Self::parse_timeline_data(data_json)
+            .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+
+        serde_json::to_string(&validation::validate(&timeline_data))
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Choose how amounts are rounded to cents: `"half_even"` (banker's, default) or `"half_up"`
+    #[wasm_bindgen]
+    pub fn set_rounding_mode(&mut self, mode: &str) -> Result<(), JsValue> {
+        self.rounding = RoundingMode::parse(mode).map_err(|e| JsValue::from_str(&e))?;
         Ok(())
     }
 
     /// Simulate the financial and operational impact of a timeline change
     #[wasm_bindgen]
     pub fn simulate_timeline_change(&self, change_json: &str) -> Result<String, JsValue> {
-        let timeline_data = self.timeline_data.as_ref()
+        let timeline_data = self
+            .timeline_data
+            .as_ref()
             .ok_or_else(|| JsValue::from_str("Timeline data not loaded"))?;
 
         let change: TimelineChange = serde_json::from_str(change_json)
             .map_err(|e| JsValue::from_str(&format!("Change parse error: {}", e)))?;
 
-        console_log!("🔄 Simulating {} on {} {}", change.change_type, change.entity_type, change.entity_id);
-
-        // Calculate financial impact
-        let financial_impact = self.calculate_financial_impact(&change, timeline_data)?;
-        
-        // Find cascade effects (dependent tasks/releases)
-        let affected_entities = self.find_affected_entities(&change, timeline_data);
-        
-        // Calculate new deadlines for dependent items
-        let new_deadlines = self.calculate_new_deadlines(&change, timeline_data)?;
-        
-        // Calculate risk score (0-100)
-        let risk_score = self.calculate_risk_score(&change, timeline_data, &affected_entities);
-        
-        // Find critical path items
-        let critical_path = self.find_critical_path(&change, timeline_data);
+        console_log!(
+            "🔄 Simulating {} on {} {}",
+            change.change_type.as_str(),
+            change.entity_type.as_str(),
+            change.entity_id
+        );
 
-        let result = TimelineSimulationResult {
-            financial_impact,
-            affected_entities: affected_entities.clone(),
-            cascade_effects: self.generate_cascade_effects(&change, &affected_entities),
-            new_deadlines,
-            risk_score,
-            revenue_change: if financial_impact > 0.0 { financial_impact } else { 0.0 },
-            expense_change: if financial_impact < 0.0 { financial_impact.abs() } else { 0.0 },
-            critical_path,
-        };
+        let (result, _) = self.simulate_sequence(timeline_data, vec![change])?;
+
+        serde_json::to_string(&result)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Simulate an ordered list of changes together, plus each change's marginal impact on
+    /// top of the ones before it. The loaded timeline is left untouched.
+    #[wasm_bindgen]
+    pub fn simulate_timeline_changes(&self, changes_json: &str) -> Result<String, JsValue> {
+        let timeline_data = self
+            .timeline_data
+            .as_ref()
+            .ok_or_else(|| JsValue::from_str("Timeline data not loaded"))?;
+
+        let changes: Vec<TimelineChange> = serde_json::from_str(changes_json)
+            .map_err(|e| JsValue::from_str(&format!("Change parse error: {}", e)))?;
+
+        console_log!("🔄 Simulating {} combined timeline changes", changes.len());
+
+        let (combined, marginal) = self.simulate_sequence(timeline_data, changes)?;
+        let result = BatchSimulationResult { combined, marginal };
+
+        serde_json::to_string(&result)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Commit a change to the loaded timeline, moving its dependents, and record an undo point
+    #[wasm_bindgen]
+    pub fn apply_timeline_change(&mut self, change_json: &str) -> Result<String, JsValue> {
+        let change: TimelineChange = serde_json::from_str(change_json)
+            .map_err(|e| JsValue::from_str(&format!("Change parse error: {}", e)))?;
+
+        let applied = self.commit_change(change)?;
+
+        console_log!("✅ Timeline change committed as version {}", self.version);
+
+        serde_json::to_string(&applied)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Revert the last committed change and return the restored version
+    #[wasm_bindgen]
+    pub fn undo(&mut self) -> Result<u32, JsValue> {
+        let mut current = self.take_snapshot()?;
+        let moved = self.history.undo(&mut current);
+        self.restore_snapshot(current);
+        if moved {
+            Ok(self.version)
+        } else {
+            Err(JsValue::from_str("Nothing to undo"))
+        }
+    }
+
+    /// Re-apply the last undone change and return the restored version
+    #[wasm_bindgen]
+    pub fn redo(&mut self) -> Result<u32, JsValue> {
+        let mut current = self.take_snapshot()?;
+        let moved = self.history.redo(&mut current);
+        self.restore_snapshot(current);
+        if moved {
+            Ok(self.version)
+        } else {
+            Err(JsValue::from_str("Nothing to redo"))
+        }
+    }
+
+    /// Versions and changes on the undo and redo stacks
+    #[wasm_bindgen]
+    pub fn get_history(&self) -> Result<String, JsValue> {
+        if self.timeline_data.is_none() {
+            return Err(JsValue::from_str("Timeline data not loaded"));
+        }
+        let state = self.history.state(self.version, self.last_change.as_ref());
+
+        serde_json::to_string(&state)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Critical path analysis: earliest/latest dates and slack for every task, release and show
+    #[wasm_bindgen]
+    pub fn analyze_schedule(&self) -> Result<String, JsValue> {
+        let timeline_data = self
+            .timeline_data
+            .as_ref()
+            .ok_or_else(|| JsValue::from_str("Timeline data not loaded"))?;
+
+        let graph = Graph::build(timeline_data)
+            .map_err(|e| JsValue::from_str(&format!("Schedule error: {}", e)))?;
+
+        serde_json::to_string(&graph.analyze(self.as_of_date()))
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Find over-allocated resources and level the schedule to their capacity, respecting
+    /// dependencies and task priorities
+    #[wasm_bindgen]
+    pub fn level_resources(&self) -> Result<String, JsValue> {
+        let timeline_data = self
+            .timeline_data
+            .as_ref()
+            .ok_or_else(|| JsValue::from_str("Timeline data not loaded"))?;
+
+        let schedule_err = |e: String| JsValue::from_str(&format!("Schedule error: {}", e));
+        let graph = Graph::build(timeline_data).map_err(schedule_err)?;
+        let report = graph
+            .level(&self.resources, &self.calendar, self.as_of_date())
+            .map_err(schedule_err)?;
+
+        serde_json::to_string(&report)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Monte Carlo over task hour estimates: the chance each release and show makes its
+    /// date, P50/P90 finish dates and the tasks driving schedule risk
+    #[wasm_bindgen]
+    pub fn simulate_schedule_risk(&self, options_json: &str) -> Result<String, JsValue> {
+        let timeline_data = self
+            .timeline_data
+            .as_ref()
+            .ok_or_else(|| JsValue::from_str("Timeline data not loaded"))?;
+
+        let options: ScheduleRiskOptions = serde_json::from_str(options_json)
+            .map_err(|e| JsValue::from_str(&format!("Parse error: {}", e)))?;
+
+        let graph = Graph::build(timeline_data)
+            .map_err(|e| JsValue::from_str(&format!("Schedule error: {}", e)))?;
+        let result = graph
+            .simulate_risk(&options, self.as_of_date())
+            .map_err(|e| JsValue::from_str(&format!("Simulation error: {}", e)))?;
 
         serde_json::to_string(&result)
             .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
@@ -434,45 +1269,86 @@ impl TimelineSimulator {
     /// Get real-time performance metrics for the timeline
     #[wasm_bindgen]
     pub fn get_timeline_metrics(&self) -> Result<String, JsValue> {
-        let timeline_data = self.timeline_data.as_ref()
+        let timeline_data = self
+            .timeline_data
+            .as_ref()
             .ok_or_else(|| JsValue::from_str("Timeline data not loaded"))?;
 
         let total_tasks = timeline_data.tasks.len();
-        let completed_tasks = timeline_data.tasks.iter()
-            .filter(|t| t.status == "completed")
+        let completed_tasks = timeline_data
+            .tasks
+            .iter()
+            .filter(|t| t.status == TaskStatus::Completed)
             .count();
-        
+
         let total_releases = timeline_data.releases.len();
-        let released = timeline_data.releases.iter()
-            .filter(|r| r.release_type == "released") // Assume status is in type for simplicity
+        let released = timeline_data
+            .releases
+            .iter()
+            .filter(|r| r.status == ReleaseStatus::Released)
             .count();
 
-        let overdue_tasks = timeline_data.tasks.iter()
-            .filter(|t| self.is_overdue(&t.deadline) && t.status != "completed")
-            .count();
+        let as_of = self.as_of_date();
+        let mut task_timing = Vec::with_capacity(total_tasks);
+        for task in &timeline_data.tasks {
+            let deadline = date::Date::parse(&task.deadline).map_err(|e| JsValue::from_str(&e))?;
+            let days = as_of.days_until(&deadline);
+            let open = !matches!(task.status, TaskStatus::Completed | TaskStatus::Cancelled);
+            task_timing.push(TaskTiming {
+                id: task.id.clone(),
+                deadline: task.deadline.clone(),
+                days_remaining: if open { days.max(0) } else { 0 },
+                days_late: if open { (-days).max(0) } else { 0 },
+                overdue: open && self.is_overdue(&deadline),
+            });
+        }
+        let overdue_tasks = task_timing.iter().filter(|t| t.overdue).count();
+        let total_days_late: i64 = task_timing.iter().map(|t| t.days_late).sum();
+
+        let mode = self.rounding;
+        let total_revenue_impact = Money::sum_f64(
+            timeline_data
+                .tasks
+                .iter()
+                .map(|t| t.revenue_impact)
+                .chain(timeline_data.releases.iter().map(|r| r.projected_revenue))
+                .chain(timeline_data.shows.iter().map(|s| s.revenue)),
+            DEFAULT_CURRENCY,
+            mode,
+        );
+
+        let total_cost_impact = Money::sum_f64(
+            timeline_data
+                .tasks
+                .iter()
+                .map(|t| t.cost_impact)
+                .chain(
+                    timeline_data
+                        .releases
+                        .iter()
+                        .flat_map(|r| [r.budget, r.marketing_spend]),
+                )
+                .chain(timeline_data.shows.iter().map(|s| s.expenses)),
+            DEFAULT_CURRENCY,
+            mode,
+        );
+
+        let net_impact = total_revenue_impact
+            .checked_sub(&total_cost_impact)
+            .map_err(|e| JsValue::from_str(&e))?;
 
-        let total_revenue_impact: f64 = timeline_data.tasks.iter()
-            .map(|t| t.revenue_impact)
-            .sum::<f64>() + 
-            timeline_data.releases.iter()
-            .map(|r| r.projected_revenue)
-            .sum::<f64>() +
-            timeline_data.shows.iter()
-            .map(|s| s.revenue)
-            .sum::<f64>();
-
-        let total_cost_impact: f64 = timeline_data.tasks.iter()
-            .map(|t| t.cost_impact)
-            .sum::<f64>() + 
-            timeline_data.releases.iter()
-            .map(|r| r.budget + r.marketing_spend)
-            .sum::<f64>() +
-            timeline_data.shows.iter()
-            .map(|s| s.expenses)
-            .sum::<f64>();
+        #[derive(Serialize)]
+        struct TaskTiming {
+            id: String,
+            deadline: String,
+            days_remaining: i64,
+            days_late: i64,
+            overdue: bool,
+        }
 
         #[derive(Serialize)]
         struct TimelineMetrics {
+            as_of: String,
             total_tasks: usize,
             completed_tasks: usize,
             completion_rate: f64,
@@ -483,28 +1359,38 @@ impl TimelineSimulator {
             total_cost_impact: f64,
             net_impact: f64,
             efficiency_score: f64,
+            total_days_late: i64,
+            task_timing: Vec<TaskTiming>,
         }
 
-        let completion_rate = if total_tasks > 0 { 
-            (completed_tasks as f64 / total_tasks as f64) * 100.0 
-        } else { 0.0 };
+        let completion_rate = if total_tasks > 0 {
+            (completed_tasks as f64 / total_tasks as f64) * 100.0
+        } else {
+            0.0
+        };
 
         let efficiency_score = if total_tasks > 0 {
-            ((completed_tasks as f64 / total_tasks as f64) * 0.6 + 
-             (1.0 - (overdue_tasks as f64 / total_tasks as f64)) * 0.4) * 100.0
-        } else { 0.0 };
+            ((completed_tasks as f64 / total_tasks as f64) * 0.6
+                + (1.0 - (overdue_tasks as f64 / total_tasks as f64)) * 0.4)
+                * 100.0
+        } else {
+            0.0
+        };
 
         let metrics = TimelineMetrics {
+            as_of: as_of.to_string(),
             total_tasks,
             completed_tasks,
             completion_rate,
             total_releases,
             released_count: released,
             overdue_tasks,
-            total_revenue_impact,
-            total_cost_impact,
-            net_impact: total_revenue_impact - total_cost_impact,
+            total_revenue_impact: total_revenue_impact.to_f64(),
+            total_cost_impact: total_cost_impact.to_f64(),
+            net_impact: net_impact.to_f64(),
             efficiency_score,
+            total_days_late,
+            task_timing,
         };
 
         serde_json::to_string(&metrics)
@@ -512,141 +1398,1131 @@ impl TimelineSimulator {
     }
 }
 
+// Private helper methods for FinancialEngine
+impl FinancialEngine {
+    /// Fill in tier totals and check a show's terms as it is loaded, so a bad show is
+    /// rejected up front instead of failing every later calculation
+    fn prepare_show(&self, show: &mut Show) -> Result<(), String> {
+        inventory::derive_totals(show, self.rounding)?;
+        if let Some(deal) = &show.deal {
+            deal.validate()
+                .map_err(|e| format!("Show on {}: {}", show.date, e))?;
+            if deal.needs_box_office() && show.gross_box_office.is_none() {
+                return Err(format!(
+                    "Show on {}: percentage deals need a gross_box_office figure or ticket tiers",
+                    show.date
+                ));
+            }
+        }
+        if show.travel_expenses < 0.0 || show.travel_expenses > show.expenses {
+            return Err(format!(
+                "Show on {}: travel_expenses must be between 0 and the show's expenses",
+                show.date
+            ));
+        }
+        Ok(())
+    }
+
+    /// Artist payout for a show with deal terms, in the show's currency
+    fn deal_payout(&self, show: &Show) -> Result<Option<DealPayout>, String> {
+        let deal = match &show.deal {
+            Some(deal) => deal,
+            None => return Ok(None),
+        };
+        let inputs = DealInputs {
+            tickets_sold: show.tickets_sold,
+            capacity: show.capacity,
+            gross_box_office: show.gross_box_office,
+            promoter_expenses: show.promoter_expenses,
+        };
+        deal.payout(&inputs, show.currency, self.rounding)
+            .map(Some)
+            .map_err(|e| format!("Deal error for show on {}: {}", show.date, e))
+    }
+
+    /// Commission inputs for base-currency shows, in date order so tour caps are consumed
+    /// by the earliest shows first
+    fn commission_inputs(shows: &[Show]) -> Vec<CommissionInputs<'_>> {
+        let mut order: Vec<usize> = (0..shows.len()).collect();
+        order.sort_by(|&a, &b| shows[a].date.cmp(&shows[b].date));
+        order
+            .iter()
+            .map(|&i| CommissionInputs {
+                show_index: i,
+                date: &shows[i].date,
+                fee: shows[i].revenue,
+                expenses: shows[i].expenses,
+                income: &shows[i].other_income,
+            })
+            .collect()
+    }
+
+    /// Tour net profit after withholding tax and commissions, with one driver moved by
+    /// `change` percent. `shows` are the resolved shows in the base currency.
+    fn sensitivity_profit(
+        &self,
+        shows: &[Show],
+        driver: Driver,
+        change: f64,
+    ) -> Result<f64, String> {
+        let factor = 1.0 + change / 100.0;
+        let base = self.base_currency;
+        let mode = self.rounding;
+        let inputs = self.demand_inputs(shows);
+        let elasticities = self.demand.elasticities(&inputs);
+
+        let mut adjusted = shows.to_vec();
+        for ((show, original), input) in adjusted.iter_mut().zip(&self.shows).zip(&inputs) {
+            match driver {
+                Driver::TicketPrice if show.tickets_sold > 0 => {
+                    let elasticity = demand::elasticity_for(&elasticities, input.market);
+                    let tickets = demand::projected_tickets(
+                        self.demand.curve,
+                        elasticity,
+                        input,
+                        factor,
+                        1.0,
+                    );
+                    show.revenue = self
+                        .projected_revenue(show, tickets, factor, show.capacity)?
+                        .to_f64();
+                }
+                Driver::Attendance if show.tickets_sold > 0 => {
+                    let mut tickets = show.tickets_sold as f64 * factor;
+                    if show.capacity > 0 {
+                        tickets = tickets.min(show.capacity as f64);
+                    }
+                    show.revenue = self
+                        .projected_revenue(show, tickets.floor() as u32, 1.0, show.capacity)?
+                        .to_f64();
+                }
+                Driver::Expenses => show.expenses *= factor,
+                Driver::FxRate if original.currency != base => {
+                    show.revenue *= factor;
+                    show.expenses *= factor;
+                    show.travel_expenses *= factor;
+                    for line in &mut show.other_income {
+                        line.amount *= factor;
+                    }
+                }
+                Driver::TravelCost => show.expenses += show.travel_expenses * (factor - 1.0),
+                _ => {}
+            }
+        }
+
+        let wht_factor = if driver == Driver::Wht { factor } else { 1.0 };
+        let mut net = Money::zero(base);
+        for show in &adjusted {
+            let revenue = Money::from_f64(show.revenue, base, mode);
+            let expenses = Money::from_f64(show.expenses, base, mode);
+            let tax = self.tax_rules.show_tax(
+                show.country.as_deref(),
+                show.treaty_relief,
+                revenue,
+                expenses,
+                mode,
+            )?;
+            let wht = Money::from_f64(tax.wht, base, mode).multiply(wht_factor, mode)?;
+            net = net
+                .checked_add(&revenue)?
+                .checked_sub(&expenses)?
+                .checked_sub(&wht)?;
+        }
+
+        let mut agreements = self.commissions.clone();
+        if driver == Driver::Commission {
+            for agreement in &mut agreements {
+                agreement.rate = (agreement.rate * factor).clamp(0.0, 100.0);
+                for tier in &mut agreement.tiers {
+                    tier.rate = (tier.rate * factor).clamp(0.0, 100.0);
+                }
+            }
+        }
+        let commissions =
+            commission::calculate(&agreements, &Self::commission_inputs(&adjusted), base, mode)?;
+        net = net.checked_sub(&Money::from_f64(commissions.total, base, mode))?;
+        Ok(net.to_f64())
+    }
+
+    /// Current ticket sales per show, with the price taken as the tiers' average face value,
+    /// or the gross box office (revenue without one) per ticket sold for shows without tiers
+    fn demand_inputs<'a>(&self, shows: &'a [Show]) -> Vec<DemandInputs<'a>> {
+        shows
+            .iter()
+            .map(|show| {
+                let gross = if show.tiers.is_empty() {
+                    show.gross_box_office.unwrap_or(show.revenue)
+                } else {
+                    show.tiers
+                        .iter()
+                        .map(|t| t.face_value * t.sold as f64)
+                        .sum()
+                };
+                DemandInputs {
+                    market: show.market.as_deref().or(show.country.as_deref()),
+                    capacity: show.capacity,
+                    tickets_sold: show.tickets_sold,
+                    ticket_price: if show.tickets_sold > 0 {
+                        gross / show.tickets_sold as f64
+                    } else {
+                        0.0
+                    },
+                }
+            })
+            .collect()
+    }
+
+    /// Ticket gross a show's current price is taken from: for deal shows, whose revenue is
+    /// the payout, the gross box office
+    fn box_office(show: &Show) -> f64 {
+        match show.deal {
+            Some(_) => show.gross_box_office.unwrap_or(show.revenue),
+            None => show.revenue,
+        }
+    }
+
+    /// What a resolved show with ticket sales earns selling `tickets` at `price_multiplier`
+    /// times its current price. Deal shows are paid out on the projected gross, as in pricing.
+    fn projected_revenue(
+        &self,
+        show: &Show,
+        tickets: u32,
+        price_multiplier: f64,
+        capacity: u32,
+    ) -> Result<Money, String> {
+        let base = self.base_currency;
+        let mode = self.rounding;
+        let gross = Money::from_f64(Self::box_office(show), base, mode)
+            .divide(show.tickets_sold as i64, mode)
+            .multiply(price_multiplier, mode)?
+            .times(tickets as i64)?;
+        match &show.deal {
+            Some(deal) => {
+                let inputs = DealInputs {
+                    tickets_sold: tickets,
+                    capacity,
+                    gross_box_office: Some(gross.to_f64()),
+                    promoter_expenses: show.promoter_expenses,
+                };
+                let payout = deal
+                    .payout(&inputs, base, mode)
+                    .map_err(|e| format!("Deal error for show on {}: {}", show.date, e))?;
+                Ok(Money::from_f64(payout.payout, base, mode))
+            }
+            None => Ok(gross),
+        }
+    }
+
+    /// Project each show under percentage changes to ticket price, capacity and expenses,
+    /// with ticket sales following the configured demand curve
+    fn project_scenario(
+        &self,
+        shows: &[Show],
+        ticket_price_change: f64,
+        capacity_change: f64,
+        expense_change: f64,
+    ) -> Result<ScenarioResult, JsValue> {
+        // Calculate current totals
+        let mode = self.rounding;
+        let base = self.base_currency;
+        let current_revenue = Money::sum_f64(shows.iter().map(|s| s.revenue), base, mode);
+        let current_expenses = Money::sum_f64(shows.iter().map(|s| s.expenses), base, mode);
+        let current_tickets = inventory::ticket_total(shows.iter().map(|s| s.tickets_sold))
+            .map_err(|e| JsValue::from_str(&e))?;
+
+        // Calculate average ticket price
+        let avg_ticket_price = if current_tickets > 0 {
+            Money::sum_f64(shows.iter().map(Self::box_office), base, mode)
+                .divide(current_tickets as i64, mode)
+        } else {
+            Money::parse("50", base, mode).map_err(|e| JsValue::from_str(&e))? // Default
+        };
+
+        // Apply scenario changes
+        let price_multiplier = 1.0 + ticket_price_change / 100.0;
+        let capacity_multiplier = 1.0 + capacity_change / 100.0;
+        let expense_multiplier = 1.0 + expense_change / 100.0;
+
+        let inputs = self.demand_inputs(shows);
+        let elasticities = self.demand.elasticities(&inputs);
+        let to_js = |e: String| JsValue::from_str(&e);
+        let new_ticket_price = avg_ticket_price
+            .multiply(price_multiplier, mode)
+            .map_err(to_js)?;
+
+        let mut show_results = Vec::with_capacity(shows.len());
+        let mut projected_revenue = Money::zero(base);
+        let mut projected_expenses = Money::zero(base);
+        let mut projected_tickets: u32 = 0;
+        for (index, (show, input)) in shows.iter().zip(&inputs).enumerate() {
+            let revenue = Money::from_f64(show.revenue, base, mode);
+            let elasticity = demand::elasticity_for(&elasticities, input.market);
+            let capacity = (show.capacity as f64 * capacity_multiplier.max(0.0)).floor() as u32;
+            let (tickets, show_revenue) = if show.tickets_sold > 0 {
+                let tickets = demand::projected_tickets(
+                    self.demand.curve,
+                    elasticity,
+                    input,
+                    price_multiplier,
+                    capacity_multiplier,
+                );
+                let revenue = self
+                    .projected_revenue(show, tickets, price_multiplier, capacity)
+                    .map_err(to_js)?;
+                (tickets, revenue)
+            } else {
+                // Flat fees without ticket sales do not move with the ticket price
+                (0, revenue)
+            };
+            let expenses = Money::from_f64(show.expenses, base, mode)
+                .multiply(expense_multiplier, mode)
+                .map_err(to_js)?;
+
+            projected_revenue = projected_revenue
+                .checked_add(&show_revenue)
+                .map_err(to_js)?;
+            projected_expenses = projected_expenses.checked_add(&expenses).map_err(to_js)?;
+            projected_tickets =
+                inventory::ticket_total([projected_tickets, tickets]).map_err(to_js)?;
+            show_results.push(ShowScenario {
+                show_index: index,
+                date: show.date.clone(),
+                market: input.market.map(demand::market_key),
+                elasticity,
+                capacity,
+                current_tickets: show.tickets_sold,
+                projected_tickets: tickets,
+                current_revenue: revenue.to_f64(),
+                projected_revenue: show_revenue.to_f64(),
+                projected_expenses: expenses.to_f64(),
+                projected_profit: show_revenue.checked_sub(&expenses).map_err(to_js)?.to_f64(),
+            });
+        }
+
+        let projected_profit = projected_revenue
+            .checked_sub(&projected_expenses)
+            .map_err(to_js)?;
+        let current_profit = current_revenue
+            .checked_sub(&current_expenses)
+            .map_err(to_js)?;
+        let profit_change = if current_profit.minor_units() != 0 {
+            ((projected_profit.minor_units() - current_profit.minor_units()) as f64
+                / current_profit.minor_units().abs() as f64)
+                * 100.0
+        } else if projected_profit.minor_units() > 0 {
+            100.0
+        } else {
+            -100.0
+        };
+
+        Ok(ScenarioResult {
+            current_revenue: current_revenue.to_f64(),
+            current_expenses: current_expenses.to_f64(),
+            current_profit: current_profit.to_f64(),
+            projected_revenue: projected_revenue.to_f64(),
+            projected_expenses: projected_expenses.to_f64(),
+            projected_profit: projected_profit.to_f64(),
+            profit_change_percent: profit_change,
+            new_ticket_price: new_ticket_price.to_f64(),
+            projected_tickets,
+            curve: self.demand.curve,
+            elasticities,
+            shows: show_results,
+        })
+    }
+
+    /// Copies of the loaded shows with deal-derived revenue, converted (deal terms included)
+    /// to the base currency at each show's date
+    fn resolved_shows(&self) -> Result<Vec<Show>, JsValue> {
+        let base = self.base_currency;
+        let mode = self.rounding;
+        self.shows
+            .iter()
+            .map(|show| {
+                let mut resolved = show.clone();
+                if let Some(payout) = self.deal_payout(show).map_err(|e| JsValue::from_str(&e))? {
+                    resolved.revenue = payout.payout;
+                }
+                if show.currency == base {
+                    return Ok(resolved);
+                }
+                let date = date::Date::parse(&show.date).map_err(|e| JsValue::from_str(&e))?;
+                let convert = |amount: f64| {
+                    self.fx_rates
+                        .convert(
+                            Money::from_f64(amount, show.currency, mode),
+                            base,
+                            &date,
+                            mode,
+                        )
+                        .map(|m| m.to_f64())
+                        .map_err(|e| JsValue::from_str(&format!("FX error: {}", e)))
+                };
+                resolved.revenue = convert(resolved.revenue)?;
+                resolved.expenses = convert(resolved.expenses)?;
+                resolved.travel_expenses = convert(resolved.travel_expenses)?;
+                resolved.promoter_expenses = convert(resolved.promoter_expenses)?;
+                resolved.gross_box_office = resolved.gross_box_office.map(convert).transpose()?;
+                if let Some(deal) = &mut resolved.deal {
+                    deal.map_amounts(convert)?;
+                }
+                for line in &mut resolved.other_income {
+                    line.amount = convert(line.amount)?;
+                }
+                for tier in &mut resolved.tiers {
+                    tier.face_value = convert(tier.face_value)?;
+                    tier.fees = convert(tier.fees)?;
+                }
+                resolved.currency = base;
+                Ok(resolved)
+            })
+            .collect()
+    }
+}
+
 // Private helper methods for TimelineSimulator
 impl TimelineSimulator {
-    fn calculate_financial_impact(&self, change: &TimelineChange, timeline_data: &TimelineData) -> Result<f64, JsValue> {
-        match change.entity_type.as_str() {
-            "task" => {
-                if let Some(task) = timeline_data.tasks.iter().find(|t| t.id == change.entity_id) {
-                    match change.change_type.as_str() {
-                        "delay" => Ok(-task.revenue_impact * 0.1), // 10% revenue loss for delays
-                        "complete" => Ok(task.revenue_impact),
-                        "cancel" => Ok(-task.revenue_impact - task.cost_impact),
-                        _ => Ok(0.0),
+    /// Parse timeline JSON, migrating legacy release payloads first
+    fn parse_timeline_data(data_json: &str) -> Result<TimelineData, serde_json::Error> {
+        let mut value: serde_json::Value = serde_json::from_str(data_json)?;
+        status::migrate_legacy_releases(&mut value);
+        serde_json::from_value(value)
+    }
+
+    fn calculate_financial_impact(
+        &self,
+        change: &TimelineChange,
+        timeline_data: &TimelineData,
+    ) -> Result<f64, JsValue> {
+        match change.entity_type {
+            EntityKind::Task => {
+                if let Some(task) = timeline_data
+                    .tasks
+                    .iter()
+                    .find(|t| t.id == change.entity_id)
+                {
+                    match change.change_type {
+                        ChangeType::Delay => Ok(-task.revenue_impact * 0.1), // 10% revenue loss for delays
+                        ChangeType::Complete if task.status == TaskStatus::Completed => Ok(0.0),
+                        ChangeType::Complete => Ok(task.revenue_impact),
+                        ChangeType::Cancel => Ok(-task.revenue_impact - task.cost_impact),
+                        ChangeType::Reschedule => Ok(0.0),
                     }
                 } else {
                     Err(JsValue::from_str("Task not found"))
                 }
-            },
-            "release" => {
-                if let Some(release) = timeline_data.releases.iter().find(|r| r.id == change.entity_id) {
-                    match change.change_type.as_str() {
-                        "delay" => Ok(-release.projected_revenue * 0.2), // 20% revenue loss for release delays
-                        "complete" => Ok(release.projected_revenue - release.budget - release.marketing_spend),
-                        "cancel" => Ok(-release.budget - release.marketing_spend),
-                        _ => Ok(0.0),
+            }
+            EntityKind::Release => {
+                if let Some(release) = timeline_data
+                    .releases
+                    .iter()
+                    .find(|r| r.id == change.entity_id)
+                {
+                    match change.change_type {
+                        ChangeType::Delay => Ok(-release.projected_revenue * 0.2), // 20% revenue loss for release delays
+                        ChangeType::Complete if release.status == ReleaseStatus::Released => {
+                            Ok(0.0)
+                        }
+                        ChangeType::Complete => Ok(release.projected_revenue
+                            - release.budget
+                            - release.marketing_spend),
+                        ChangeType::Cancel => Ok(-release.budget - release.marketing_spend),
+                        ChangeType::Reschedule => Ok(0.0),
                     }
                 } else {
                     Err(JsValue::from_str("Release not found"))
                 }
-            },
-            "show" => {
-                if let Some(show) = timeline_data.shows.iter().find(|s| s.id == change.entity_id) {
-                    match change.change_type.as_str() {
-                        "delay" => Ok(-show.revenue * 0.15), // 15% revenue loss for show reschedules
-                        "cancel" => Ok(-show.revenue + show.expenses * 0.5), // Lose revenue but save some costs
-                        _ => Ok(0.0),
+            }
+            EntityKind::Show => {
+                if let Some(show) = timeline_data
+                    .shows
+                    .iter()
+                    .find(|s| s.id == change.entity_id)
+                {
+                    match change.change_type {
+                        ChangeType::Delay => Ok(-show.revenue * 0.15), // 15% revenue loss for show reschedules
+                        ChangeType::Cancel => Ok(-show.revenue + show.expenses * 0.5), // Lose revenue but save some costs
+                        ChangeType::Complete | ChangeType::Reschedule => Ok(0.0),
                     }
                 } else {
                     Err(JsValue::from_str("Show not found"))
                 }
-            },
-            _ => Ok(0.0),
+            }
         }
     }
 
-    fn find_affected_entities(&self, change: &TimelineChange, timeline_data: &TimelineData) -> Vec<String> {
-        let mut affected = Vec::new();
-
-        // Find tasks that depend on the changed entity
-        for task in &timeline_data.tasks {
-            if task.dependencies.contains(&change.entity_id) {
-                affected.push(format!("task:{}", task.id));
-            }
+    /// Revenue a delay costs stays lost, so later changes in a batch are priced on what is left
+    fn book_financial_impact(data: &mut TimelineData, change: &TimelineChange) {
+        if change.change_type != ChangeType::Delay {
+            return;
         }
-
-        // Find releases that depend on the changed entity
-        for release in &timeline_data.releases {
-            if release.dependencies.contains(&change.entity_id) {
-                affected.push(format!("release:{}", release.id));
+        let id = change.entity_id.as_str();
+        match change.entity_type {
+            EntityKind::Task => {
+                if let Some(task) = data.tasks.iter_mut().find(|t| t.id == id) {
+                    task.revenue_impact *= 0.9;
+                }
+            }
+            EntityKind::Release => {
+                if let Some(release) = data.releases.iter_mut().find(|r| r.id == id) {
+                    release.projected_revenue *= 0.8;
+                }
+            }
+            EntityKind::Show => {
+                if let Some(show) = data.shows.iter_mut().find(|s| s.id == id) {
+                    show.revenue *= 0.85;
+                }
             }
         }
+    }
+
+    /// Resolve, price and cascade one change against `current`, then apply it there and book
+    /// its financial impact. Simulated batches and committed changes both go through this.
+    fn step(
+        &self,
+        current: &mut TimelineData,
+        mut change: TimelineChange,
+    ) -> Result<MarginalImpact, JsValue> {
+        self.resolve_change(current, &mut change)?;
+        let result = self.simulate_change(current, &change)?;
+        history::apply_change(current, &change, &result.cascade)
+            .map_err(|e| JsValue::from_str(&format!("Change error: {}", e)))?;
+        Self::book_financial_impact(current, &change);
+        Ok(MarginalImpact { change, result })
+    }
 
-        affected
+    /// Apply a change to the loaded timeline and record the previous state for undo
+    fn commit_change(&mut self, change: TimelineChange) -> Result<AppliedChange, JsValue> {
+        let mut updated = self
+            .timeline_data
+            .clone()
+            .ok_or_else(|| JsValue::from_str("Timeline data not loaded"))?;
+        let MarginalImpact { change, result } = self.step(&mut updated, change)?;
+
+        let previous = self.take_snapshot()?;
+        self.version = self.history.commit(previous);
+        self.timeline_data = Some(updated);
+        self.last_change = Some(change);
+
+        Ok(AppliedChange {
+            version: self.version,
+            result,
+        })
     }
 
-    fn calculate_new_deadlines(&self, change: &TimelineChange, timeline_data: &TimelineData) -> Result<HashMap<String, String>, JsValue> {
-        let mut new_deadlines = HashMap::new();
+    /// Make `changes` one after another on a working copy of the timeline. Each change is
+    /// resolved, priced and cascaded against the state the earlier ones left, so the
+    /// per-change results add up to the combined one.
+    fn simulate_sequence(
+        &self,
+        timeline_data: &TimelineData,
+        changes: Vec<TimelineChange>,
+    ) -> Result<(TimelineSimulationResult, Vec<MarginalImpact>), JsValue> {
+        let schedule_err = |e: String| JsValue::from_str(&format!("Schedule error: {}", e));
+        let mut current = timeline_data.clone();
+        let mut steps = Vec::with_capacity(changes.len());
+        for change in changes {
+            steps.push(self.step(&mut current, change)?);
+        }
 
-        if change.change_type == "delay" {
-            if let Some(new_date) = &change.new_date {
-                // Cascade delay to dependent items (simplified - add 1 day)
-                let affected = self.find_affected_entities(change, timeline_data);
-                for entity in affected {
-                    new_deadlines.insert(entity, format!("{}", new_date)); // Simplified
+        // An entity moved by several changes goes from its first old date to its last new one
+        let mut cascade: Vec<CascadeEntry> = Vec::new();
+        for entry in steps.iter().flat_map(|s| &s.result.cascade) {
+            match cascade.iter_mut().find(|c| c.entity == entry.entity) {
+                Some(merged) => {
+                    let old_date = date::Date::parse(&merged.old_date).map_err(schedule_err)?;
+                    let new_date = date::Date::parse(&entry.new_date).map_err(schedule_err)?;
+                    merged.new_date = entry.new_date.clone();
+                    merged.slip_days = old_date.days_until(&new_date);
+                    merged.depth = merged.depth.min(entry.depth);
                 }
+                None => cascade.push(entry.clone()),
             }
         }
+        let changes: Vec<TimelineChange> = steps.iter().map(|s| s.change.clone()).collect();
+        let affected_entities: Vec<String> = cascade.iter().map(|c| c.entity.clone()).collect();
+        let financial_impact = Money::sum_f64(
+            steps.iter().map(|s| s.result.financial_impact),
+            DEFAULT_CURRENCY,
+            self.rounding,
+        )
+        .to_f64();
+        let critical_path = match steps.last() {
+            Some(step) => step.result.critical_path.clone(),
+            None => {
+                Graph::build(timeline_data)
+                    .map_err(schedule_err)?
+                    .analyze(self.as_of_date())
+                    .critical_path
+            }
+        };
 
-        Ok(new_deadlines)
+        let combined = TimelineSimulationResult {
+            financial_impact,
+            cascade_effects: self.generate_cascade_effects(&changes, &affected_entities),
+            new_deadlines: self.calculate_new_deadlines(&cascade),
+            risk_score: self.calculate_risk_score(&changes, &affected_entities),
+            affected_entities,
+            revenue_change: if financial_impact > 0.0 {
+                financial_impact
+            } else {
+                0.0
+            },
+            expense_change: if financial_impact < 0.0 {
+                financial_impact.abs()
+            } else {
+                0.0
+            },
+            critical_path,
+            cascade,
+        };
+        Ok((combined, steps))
     }
 
-    fn calculate_risk_score(&self, change: &TimelineChange, timeline_data: &TimelineData, affected_entities: &[String]) -> f64 {
-        let base_risk = match change.change_type.as_str() {
-            "delay" => 40.0,
-            "cancel" => 80.0,
-            "complete" => 0.0,
-            _ => 20.0,
-        };
+    /// Impact of one resolved change on `timeline_data`
+    fn simulate_change(
+        &self,
+        timeline_data: &TimelineData,
+        change: &TimelineChange,
+    ) -> Result<TimelineSimulationResult, JsValue> {
+        let changes = std::slice::from_ref(change);
+        let financial_impact = Money::from_f64(
+            self.calculate_financial_impact(change, timeline_data)?,
+            DEFAULT_CURRENCY,
+            self.rounding,
+        )
+        .to_f64();
+
+        let schedule_err = |e: String| JsValue::from_str(&format!("Schedule error: {}", e));
+        let mut graph = Graph::build(timeline_data).map_err(schedule_err)?;
+
+        // Propagate the change through everything downstream of it
+        let mut cascade = graph
+            .cascade(changes, self.as_of_date())
+            .map_err(schedule_err)?;
+        if change.delay_days.is_some() && change.delay_unit == DelayUnit::WorkingDays {
+            if let (Some(from), Some(to)) = (
+                Self::entity_date(timeline_data, change),
+                change.new_date.as_deref(),
+            ) {
+                let from = date::Date::parse(from).map_err(schedule_err)?;
+                let to = date::Date::parse(to).map_err(schedule_err)?;
+                self.calendar
+                    .recount_slips(&mut cascade, from, to)
+                    .map_err(schedule_err)?;
+            }
+        }
+        let affected_entities: Vec<String> = cascade.iter().map(|c| c.entity.clone()).collect();
+
+        // New dates for dependents that actually move
+        let new_deadlines = self.calculate_new_deadlines(&cascade);
+
+        // Calculate risk score (0-100)
+        let risk_score = self.calculate_risk_score(changes, &affected_entities);
+
+        // Critical path with the change applied
+        graph.apply_change(change).map_err(schedule_err)?;
+        let critical_path = graph.analyze(self.as_of_date()).critical_path;
+
+        Ok(TimelineSimulationResult {
+            financial_impact,
+            cascade_effects: self.generate_cascade_effects(changes, &affected_entities),
+            affected_entities,
+            new_deadlines,
+            risk_score,
+            revenue_change: if financial_impact > 0.0 {
+                financial_impact
+            } else {
+                0.0
+            },
+            expense_change: if financial_impact < 0.0 {
+                financial_impact.abs()
+            } else {
+                0.0
+            },
+            critical_path,
+            cascade,
+        })
+    }
+
+    fn calculate_new_deadlines(&self, cascade: &[CascadeEntry]) -> HashMap<String, String> {
+        cascade
+            .iter()
+            .filter(|c| c.slip_days > 0)
+            .map(|c| (c.entity.clone(), c.new_date.clone()))
+            .collect()
+    }
+
+    fn calculate_risk_score(
+        &self,
+        changes: &[TimelineChange],
+        affected_entities: &[String],
+    ) -> f64 {
+        // The riskiest change sets the base
+        let base_risk = changes
+            .iter()
+            .map(|change| match change.change_type {
+                ChangeType::Delay => 40.0,
+                ChangeType::Cancel => 80.0,
+                ChangeType::Complete => 0.0,
+                ChangeType::Reschedule => 20.0,
+            })
+            .fold(0.0, f64::max);
 
         let cascade_risk = (affected_entities.len() as f64) * 5.0; // 5 points per affected entity
         let total_risk = base_risk + cascade_risk;
-        
-        if total_risk > 100.0 { 100.0 } else { total_risk }
-    }
 
-    fn find_critical_path(&self, _change: &TimelineChange, timeline_data: &TimelineData) -> Vec<String> {
-        // Simplified critical path: tasks with highest revenue impact + dependencies
-        let mut critical_tasks: Vec<_> = timeline_data.tasks.iter()
-            .filter(|t| t.revenue_impact > 1000.0 || !t.dependencies.is_empty())
-            .map(|t| t.id.clone())
-            .collect();
-        
-        critical_tasks.sort();
-        critical_tasks
+        if total_risk > 100.0 {
+            100.0
+        } else {
+            total_risk
+        }
     }
 
-    fn generate_cascade_effects(&self, change: &TimelineChange, affected_entities: &[String]) -> Vec<String> {
+    fn generate_cascade_effects(
+        &self,
+        changes: &[TimelineChange],
+        affected_entities: &[String],
+    ) -> Vec<String> {
         let mut effects = Vec::new();
 
-        match change.change_type.as_str() {
-            "delay" => {
-                effects.push("Timeline compression for dependent items".to_string());
-                if affected_entities.len() > 0 {
-                    effects.push(format!("{} dependent items require rescheduling", affected_entities.len()));
+        for change in changes {
+            let change_effects = match change.change_type {
+                ChangeType::Delay => {
+                    let mut delay_effects =
+                        vec!["Timeline compression for dependent items".to_string()];
+                    if !affected_entities.is_empty() {
+                        delay_effects.push(format!(
+                            "{} dependent items require rescheduling",
+                            affected_entities.len()
+                        ));
+                    }
+                    delay_effects
                 }
-            },
-            "cancel" => {
-                effects.push("Resource reallocation required".to_string());
-                effects.push("Budget impact on dependent items".to_string());
-            },
-            "complete" => {
-                effects.push("Accelerated timeline for dependent items".to_string());
-            },
-            _ => {}
+                ChangeType::Cancel => vec![
+                    "Resource reallocation required".to_string(),
+                    "Budget impact on dependent items".to_string(),
+                ],
+                ChangeType::Complete => {
+                    vec!["Accelerated timeline for dependent items".to_string()]
+                }
+                ChangeType::Reschedule => Vec::new(),
+            };
+            for effect in change_effects {
+                if !effects.contains(&effect) {
+                    effects.push(effect);
+                }
+            }
         }
 
         effects
     }
 
-    fn is_overdue(&self, deadline: &str) -> bool {
-        // Simplified: assume current date is "2024-01-15" for demo
-        deadline < "2024-01-15"
+    /// Reject changes to cancelled entities, and turn `delay_days` into a `new_date` counted
+    /// from the entity's date in `timeline_data`
+    fn resolve_change(
+        &self,
+        timeline_data: &TimelineData,
+        change: &mut TimelineChange,
+    ) -> Result<(), JsValue> {
+        if Self::entity_cancelled(timeline_data, change) {
+            return Err(JsValue::from_str(&format!(
+                "Change error: {} '{}' is cancelled",
+                change.entity_type.as_str(),
+                change.entity_id
+            )));
+        }
+        let days = match change.delay_days {
+            Some(days) => days,
+            None => return Ok(()),
+        };
+        let change_err = |e: String| JsValue::from_str(&format!("Change error: {}", e));
+        if !change.change_type.moves_date() {
+            return Err(change_err(format!(
+                "delay_days cannot be used with a {} change",
+                change.change_type.as_str()
+            )));
+        }
+        if change.new_date.is_some() {
+            return Err(change_err(
+                "give either new_date or delay_days, not both".to_string(),
+            ));
+        }
+        let from = Self::entity_date(timeline_data, change).ok_or_else(|| {
+            change_err(format!(
+                "{} '{}' not found",
+                change.entity_type.as_str(),
+                change.entity_id
+            ))
+        })?;
+        let from = date::Date::parse(from).map_err(change_err)?;
+        change.new_date = Some(
+            self.calendar
+                .shift(from, days, change.delay_unit)
+                .map_err(change_err)?
+                .to_string(),
+        );
+        Ok(())
+    }
+
+    /// Current date of the entity a change targets
+    fn entity_date<'a>(
+        timeline_data: &'a TimelineData,
+        change: &TimelineChange,
+    ) -> Option<&'a str> {
+        let id = change.entity_id.as_str();
+        match change.entity_type {
+            EntityKind::Task => timeline_data
+                .tasks
+                .iter()
+                .find(|t| t.id == id)
+                .map(|t| t.deadline.as_str()),
+            EntityKind::Release => timeline_data
+                .releases
+                .iter()
+                .find(|r| r.id == id)
+                .map(|r| r.release_date.as_str()),
+            EntityKind::Show => timeline_data
+                .shows
+                .iter()
+                .find(|s| s.id == id)
+                .map(|s| s.date.as_str()),
+        }
+    }
+
+    /// Whether the entity a change targets has been cancelled
+    fn entity_cancelled(timeline_data: &TimelineData, change: &TimelineChange) -> bool {
+        let id = change.entity_id.as_str();
+        match change.entity_type {
+            EntityKind::Task => timeline_data
+                .tasks
+                .iter()
+                .any(|t| t.id == id && t.status == TaskStatus::Cancelled),
+            EntityKind::Release => timeline_data
+                .releases
+                .iter()
+                .any(|r| r.id == id && r.status == ReleaseStatus::Cancelled),
+            EntityKind::Show => timeline_data
+                .shows
+                .iter()
+                .any(|s| s.id == id && s.status == ShowStatus::Cancelled),
+        }
+    }
+
+    /// Move the current state out for the history stacks
+    fn take_snapshot(&mut self) -> Result<Snapshot, JsValue> {
+        let data = self
+            .timeline_data
+            .take()
+            .ok_or_else(|| JsValue::from_str("Timeline data not loaded"))?;
+        Ok(Snapshot {
+            version: self.version,
+            data,
+            change: self.last_change.take(),
+        })
+    }
+
+    fn restore_snapshot(&mut self, snapshot: Snapshot) {
+        self.version = snapshot.version;
+        self.timeline_data = Some(snapshot.data);
+        self.last_change = snapshot.change;
+    }
+
+    fn as_of_date(&self) -> date::Date {
+        self.as_of.unwrap_or_else(date::Date::today)
+    }
+
+    fn is_overdue(&self, deadline: &date::Date) -> bool {
+        *deadline < self.as_of_date()
     }
 }
 
 // Initialize the WASM module
 #[wasm_bindgen(start)]
 pub fn main() {
-    console_log!("🦀 WASM Financial Engine + Timeline Simulator loaded - Ready for 10x performance!");
-}
\ No newline at end of file
+    console_log!(
+        "🦀 WASM Financial Engine + Timeline Simulator loaded - Ready for 10x performance!"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schedule::tests::{date, show, task};
+
+    fn simulator(data: TimelineData) -> TimelineSimulator {
+        TimelineSimulator {
+            timeline_data: Some(data),
+            rounding: RoundingMode::default(),
+            as_of: Some(date("2025-01-06")),
+            calendar: WorkingCalendar::default(),
+            resources: Vec::new(),
+            version: 0,
+            last_change: None,
+            history: History::default(),
+        }
+    }
+
+    fn engine(shows: serde_json::Value) -> FinancialEngine {
+        let tables = serde_json::from_value(serde_json::json!([
+            { "date": "2025-01", "base": "EUR", "rates": { "USD": 1.25 } }
+        ]))
+        .unwrap();
+        FinancialEngine {
+            shows: serde_json::from_value(shows).unwrap(),
+            rounding: RoundingMode::default(),
+            base_currency: DEFAULT_CURRENCY,
+            fx_rates: FxRates::from_tables(tables).unwrap(),
+            tax_rules: TaxRules::default(),
+            commissions: Vec::new(),
+            demand: DemandOptions::default(),
+        }
+    }
+
+    fn change(
+        change_type: ChangeType,
+        entity_type: EntityKind,
+        id: &str,
+        delay_days: Option<i64>,
+    ) -> TimelineChange {
+        TimelineChange {
+            change_type,
+            entity_type,
+            entity_id: id.to_string(),
+            new_date: None,
+            new_status: None,
+            new_completion: None,
+            delay_days,
+            delay_unit: DelayUnit::CalendarDays,
+        }
+    }
+
+    #[test]
+    fn batch_prices_each_change_on_the_state_before_it() {
+        let mut gig = show("gig", "2025-03-01", &[]);
+        gig.revenue = 1000.0;
+        gig.expenses = 200.0;
+        let sim = simulator(TimelineData {
+            tasks: Vec::new(),
+            releases: Vec::new(),
+            shows: vec![gig],
+        });
+        let data = sim.timeline_data.as_ref().unwrap();
+        let changes = vec![
+            change(ChangeType::Delay, EntityKind::Show, "gig", Some(7)),
+            change(ChangeType::Cancel, EntityKind::Show, "gig", None),
+        ];
+        let (combined, marginal) = sim
+            .simulate_sequence(data, changes)
+            .unwrap_or_else(|_| panic!());
+        assert_eq!(marginal[0].result.financial_impact, -150.0);
+        assert_eq!(marginal[1].result.financial_impact, -750.0);
+        // Same as cancelling outright: the delay's loss is not counted twice
+        assert_eq!(combined.financial_impact, -900.0);
+    }
+
+    #[test]
+    fn committed_changes_match_the_batch() {
+        let mut gig = show("gig", "2025-03-01", &[]);
+        gig.revenue = 1000.0;
+        gig.expenses = 200.0;
+        let mut sim = simulator(TimelineData {
+            tasks: Vec::new(),
+            releases: Vec::new(),
+            shows: vec![gig],
+        });
+        let changes = vec![
+            change(ChangeType::Delay, EntityKind::Show, "gig", Some(7)),
+            change(ChangeType::Cancel, EntityKind::Show, "gig", None),
+        ];
+        let data = sim.timeline_data.clone().unwrap();
+        let (combined, _) = sim
+            .simulate_sequence(&data, changes.clone())
+            .unwrap_or_else(|_| panic!());
+
+        let committed: Vec<f64> = changes
+            .into_iter()
+            .map(|c| {
+                sim.commit_change(c)
+                    .unwrap_or_else(|_| panic!())
+                    .result
+                    .financial_impact
+            })
+            .collect();
+        assert_eq!(committed, vec![-150.0, -750.0]);
+        assert_eq!(committed.iter().sum::<f64>(), combined.financial_impact);
+
+        // The cancelled show is still in the export
+        let exported = sim.export_timeline_data().unwrap_or_else(|_| panic!());
+        let exported: serde_json::Value = serde_json::from_str(&exported).unwrap();
+        assert_eq!(exported["shows"][0]["id"], "gig");
+        assert_eq!(exported["shows"][0]["status"], "cancelled");
+    }
+
+    #[test]
+    fn repeated_delays_add_up_in_the_combined_cascade() {
+        let data = TimelineData {
+            tasks: vec![
+                task("a", "2025-01-07", 16.0, &[]),
+                task("b", "2025-01-08", 8.0, &["a"]),
+            ],
+            releases: Vec::new(),
+            shows: Vec::new(),
+        };
+        let sim = simulator(data);
+        let data = sim.timeline_data.as_ref().unwrap();
+        let changes = vec![
+            change(ChangeType::Delay, EntityKind::Task, "a", Some(2)),
+            change(ChangeType::Delay, EntityKind::Task, "a", Some(2)),
+        ];
+        let (combined, marginal) = sim
+            .simulate_sequence(data, changes)
+            .unwrap_or_else(|_| panic!());
+        assert_eq!(marginal[1].change.new_date.as_deref(), Some("2025-01-11"));
+        let b = &combined.cascade[0];
+        assert_eq!(
+            (b.old_date.as_str(), b.new_date.as_str(), b.slip_days),
+            ("2025-01-08", "2025-01-12", 4)
+        );
+        let sum: i64 = marginal.iter().map(|m| m.result.cascade[0].slip_days).sum();
+        assert_eq!(sum, b.slip_days);
+    }
+
+    #[test]
+    fn resolved_shows_convert_box_office_figures() {
+        let engine = engine(serde_json::json!([{
+            "date": "2025-03-01", "currency": "USD", "revenue": 0, "expenses": "250.00",
+            "capacity": 1000, "tickets_sold": 800,
+            "gross_box_office": "20000.00", "promoter_expenses": 5000,
+            "deal": { "type": "versus", "guarantee": 5000, "percentage": 50, "cap": "10000" }
+        }]));
+        let shows = engine.resolved_shows().unwrap_or_else(|_| panic!());
+        let show = &shows[0];
+        assert_eq!(show.currency, DEFAULT_CURRENCY);
+        assert_eq!(show.revenue, 6000.0);
+        assert_eq!(show.expenses, 200.0);
+        assert_eq!(show.gross_box_office, Some(16000.0));
+        assert_eq!(show.promoter_expenses, 4000.0);
+        let deal = show.deal.as_ref().unwrap();
+        assert!(
+            matches!(deal.structure, DealStructure::Versus { guarantee, .. } if guarantee == 4000.0)
+        );
+        assert_eq!(deal.cap, Some(8000.0));
+    }
+
+    #[test]
+    fn deal_shows_are_checked_on_load() {
+        let engine = engine(serde_json::json!([]));
+        let load = |value: serde_json::Value| {
+            let mut show: Show = serde_json::from_value(value).unwrap();
+            engine.prepare_show(&mut show).map(|_| show)
+        };
+        // No placeholder revenue needed when the deal sets it
+        let flat = load(serde_json::json!({
+            "date": "2025-03-01", "expenses": 100,
+            "deal": { "type": "flat", "guarantee": 2000 }
+        }));
+        assert_eq!(flat.unwrap().revenue, 0.0);
+        let tiered = load(serde_json::json!({
+            "date": "2025-03-01", "expenses": 100,
+            "tiers": [{ "name": "ga", "capacity": 100, "face_value": 20, "sold": 50 }],
+            "deal": { "type": "door", "percentage": 80 }
+        }));
+        assert_eq!(tiered.unwrap().gross_box_office, Some(1000.0));
+
+        let no_gross = load(serde_json::json!({
+            "date": "2025-03-01", "expenses": 100,
+            "deal": { "type": "door", "percentage": 80 }
+        }));
+        assert!(no_gross.is_err_and(|e| e.contains("gross_box_office")));
+        let bad_terms = load(serde_json::json!({
+            "date": "2025-03-01", "expenses": 100, "gross_box_office": 5000,
+            "deal": { "type": "versus", "guarantee": -1, "percentage": 80 }
+        }));
+        assert!(bad_terms.is_err());
+    }
+
+    #[test]
+    fn scenarios_pay_deal_shows_out_on_the_projected_gross() {
+        let engine = engine(serde_json::json!([
+            {
+                "date": "2025-03-01", "expenses": 100, "capacity": 1000, "tickets_sold": 400,
+                "gross_box_office": 8000, "deal": { "type": "flat", "guarantee": 3000 }
+            },
+            {
+                "date": "2025-03-02", "expenses": 100, "capacity": 1000, "tickets_sold": 400,
+                "gross_box_office": 8000,
+                "deal": { "type": "door", "percentage": 50, "basis": "gross_box_office" }
+            }
+        ]));
+        let shows = engine.resolved_shows().unwrap_or_else(|_| panic!());
+        let result = engine
+            .project_scenario(&shows, 10.0, 0.0, 0.0)
+            .unwrap_or_else(|_| panic!());
+        // A flat fee does not move with the ticket price
+        assert_eq!(result.shows[0].projected_revenue, 3000.0);
+        // The door share follows the projected gross: half of 22.00 per ticket
+        let door = &result.shows[1];
+        assert_eq!(door.projected_revenue, door.projected_tickets as f64 * 11.0);
+        assert_eq!(result.new_ticket_price, 22.0);
+    }
+
+    #[test]
+    fn sensitivity_moves_deal_shows_through_their_terms() {
+        let engine = engine(serde_json::json!([{
+            "date": "2025-03-01", "expenses": 100, "capacity": 1000, "tickets_sold": 400,
+            "gross_box_office": 8000, "deal": { "type": "flat", "guarantee": 3000 }
+        }]));
+        let shows = engine.resolved_shows().unwrap_or_else(|_| panic!());
+        let profit = |driver, change| engine.sensitivity_profit(&shows, driver, change).unwrap();
+        let base = profit(Driver::TicketPrice, 0.0);
+        assert_eq!(base, 2900.0);
+        assert_eq!(profit(Driver::TicketPrice, 20.0), base);
+        assert_eq!(profit(Driver::Attendance, -20.0), base);
+    }
+
+    #[test]
+    fn travel_expenses_cannot_exceed_expenses() {
+        let engine = engine(serde_json::json!([]));
+        let mut show: Show = serde_json::from_value(serde_json::json!({
+            "date": "2025-03-01", "revenue": 1000, "expenses": 100, "travel_expenses": 150
+        }))
+        .unwrap();
+        assert!(engine.prepare_show(&mut show).is_err());
+        show.travel_expenses = 100.0;
+        assert!(engine.prepare_show(&mut show).is_ok());
+    }
+
+    #[test]
+    fn deal_show_metrics_price_tickets_on_the_gross() {
+        let engine = engine(serde_json::json!([
+            {
+                "date": "2025-03-01", "expenses": 100, "capacity": 1000, "tickets_sold": 400,
+                "gross_box_office": 8000, "deal": { "type": "door", "percentage": 50 }
+            },
+            { "date": "2025-03-02", "revenue": 3000, "expenses": 100, "capacity": 500, "tickets_sold": 100 }
+        ]));
+        let metrics = engine.calculate_metrics().unwrap_or_else(|_| panic!());
+        let metrics: serde_json::Value = serde_json::from_str(&metrics).unwrap();
+        assert_eq!(metrics["total_revenue"], 7000.0);
+        assert_eq!(metrics["average_ticket_price"], 22.0);
+        assert_eq!(metrics["gross_potential"], 35000.0);
+    }
+
+    #[test]
+    fn legacy_released_type_loads_as_released() {
+        let json = r#"{
+            "tasks": [],
+            "shows": [],
+            "releases": [{
+                "id": "lp", "release_type": "released", "release_date": "2025-01-03",
+                "budget": "1000.00", "projected_revenue": 5000, "platforms": [],
+                "marketing_spend": 0, "dependencies": []
+            }]
+        }"#;
+        let data = TimelineSimulator::parse_timeline_data(json).unwrap();
+        assert_eq!(data.releases[0].release_type, ReleaseType::Other);
+        assert_eq!(data.releases[0].status, ReleaseStatus::Released);
+        assert_eq!(data.releases[0].budget, 1000.0);
+    }
+}
@@ -0,0 +1,532 @@
+use std::fmt;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+/// Currency assumed for amounts that do not carry one
+pub const DEFAULT_CURRENCY: Currency = Currency(*b"EUR");
+
+/// How amounts with more precision than the currency's minor unit are rounded
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// Banker's rounding: ties go to the even neighbour
+    #[default]
+    #[serde(alias = "bankers", alias = "banker")]
+    HalfEven,
+    /// Commercial rounding: ties go away from zero
+    HalfUp,
+}
+
+impl RoundingMode {
+    pub fn parse(value: &str) -> Result<RoundingMode, String> {
+        match value {
+            "half_even" | "bankers" | "banker" => Ok(RoundingMode::HalfEven),
+            "half_up" => Ok(RoundingMode::HalfUp),
+            other => Err(format!(
+                "Unknown rounding mode '{}': expected 'half_even' or 'half_up'",
+                other
+            )),
+        }
+    }
+
+    /// Round `numerator / denominator` to an integer (`denominator` > 0)
+    fn divide(&self, numerator: i128, denominator: i128) -> i128 {
+        let quotient = numerator.div_euclid(denominator);
+        let remainder = numerator.rem_euclid(denominator);
+        let twice = remainder * 2;
+        let round_up = match twice.cmp(&denominator) {
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Less => false,
+            std::cmp::Ordering::Equal => match self {
+                RoundingMode::HalfEven => quotient % 2 != 0,
+                // Ties away from zero: for negatives the floor is already away from zero
+                RoundingMode::HalfUp => numerator >= 0,
+            },
+        };
+        if round_up {
+            quotient + 1
+        } else {
+            quotient
+        }
+    }
+}
+
+/// ISO 4217 currency code
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Currency([u8; 3]);
+
+impl Currency {
+    pub fn parse(code: &str) -> Result<Currency, String> {
+        let bytes = code.trim().as_bytes();
+        if bytes.len() != 3 || !bytes.iter().all(|b| b.is_ascii_alphabetic()) {
+            return Err(format!("Invalid currency code '{}'", code));
+        }
+        Ok(Currency([
+            bytes[0].to_ascii_uppercase(),
+            bytes[1].to_ascii_uppercase(),
+            bytes[2].to_ascii_uppercase(),
+        ]))
+    }
+
+    pub fn code(&self) -> &str {
+        std::str::from_utf8(&self.0).unwrap_or("???")
+    }
+
+    /// Number of decimal places in the currency's minor unit
+    pub fn decimals(&self) -> u32 {
+        match self.code() {
+            "JPY" | "KRW" | "CLP" | "ISK" | "VND" => 0,
+            "BHD" | "KWD" | "OMR" | "JOD" | "TND" => 3,
+            _ => 2,
+        }
+    }
+
+    fn scale(&self) -> i128 {
+        10i128.pow(self.decimals())
+    }
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        DEFAULT_CURRENCY
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Currency::parse(&code).map_err(de::Error::custom)
+    }
+}
+
+fn too_large() -> String {
+    "Amount is too large".to_string()
+}
+
+/// Fixed-point monetary amount stored as an integer count of the currency's minor unit
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Money {
+    minor_units: i64,
+    currency: Currency,
+}
+
+impl Money {
+    pub fn zero(currency: Currency) -> Money {
+        Money {
+            minor_units: 0,
+            currency,
+        }
+    }
+
+    pub fn from_minor_units(minor_units: i64, currency: Currency) -> Money {
+        Money {
+            minor_units,
+            currency,
+        }
+    }
+
+    /// Parse a decimal string such as `"-1234.565"`, rounding excess precision with `mode`
+    pub fn parse(value: &str, currency: Currency, mode: RoundingMode) -> Result<Money, String> {
+        let out_of_range = || format!("Amount '{}' is out of range", value);
+        let (mantissa, exponent) = parse_decimal(value)?;
+        let shift = exponent
+            .checked_add(currency.decimals() as i32)
+            .ok_or_else(out_of_range)?;
+        let power = 10i128
+            .checked_pow(shift.unsigned_abs())
+            .ok_or_else(out_of_range)?;
+        let minor = if shift >= 0 {
+            mantissa.checked_mul(power).ok_or_else(out_of_range)?
+        } else {
+            mode.divide(mantissa, power)
+        };
+        let minor_units = i64::try_from(minor).map_err(|_| out_of_range())?;
+        Ok(Money {
+            minor_units,
+            currency,
+        })
+    }
+
+    /// Convert a float via its shortest round-tripping decimal form, so `0.1` is exactly 10 cents
+    pub fn from_f64(amount: f64, currency: Currency, mode: RoundingMode) -> Money {
+        if !amount.is_finite() {
+            return Money::zero(currency);
+        }
+        Money::parse(&amount.to_string(), currency, mode).unwrap_or_else(|_| {
+            let scaled = amount * currency.scale() as f64;
+            Money::from_minor_units(scaled.round() as i64, currency)
+        })
+    }
+
+    pub fn minor_units(&self) -> i64 {
+        self.minor_units
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.minor_units as f64 / self.currency.scale() as f64
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.minor_units < 0
+    }
+
+    fn same_currency(&self, other: &Money) -> Result<(), String> {
+        if self.currency == other.currency {
+            Ok(())
+        } else {
+            Err(format!(
+                "Currency mismatch: {} and {}",
+                self.currency, other.currency
+            ))
+        }
+    }
+
+    pub fn checked_add(&self, other: &Money) -> Result<Money, String> {
+        self.same_currency(other)?;
+        let minor_units = self
+            .minor_units
+            .checked_add(other.minor_units)
+            .ok_or_else(too_large)?;
+        Ok(Money::from_minor_units(minor_units, self.currency))
+    }
+
+    pub fn checked_sub(&self, other: &Money) -> Result<Money, String> {
+        self.same_currency(other)?;
+        let minor_units = self
+            .minor_units
+            .checked_sub(other.minor_units)
+            .ok_or_else(too_large)?;
+        Ok(Money::from_minor_units(minor_units, self.currency))
+    }
+
+    pub fn negate(&self) -> Money {
+        Money::from_minor_units(-self.minor_units, self.currency)
+    }
+
+    /// Multiply by an integer quantity (e.g. tickets), exactly
+    pub fn times(&self, quantity: i64) -> Result<Money, String> {
+        let minor_units = self
+            .minor_units
+            .checked_mul(quantity)
+            .ok_or_else(too_large)?;
+        Ok(Money::from_minor_units(minor_units, self.currency))
+    }
+
+    /// Multiply by an arbitrary factor (percentages, FX rates), rounding the result
+    pub fn multiply(&self, factor: f64, mode: RoundingMode) -> Result<Money, String> {
+        // Express the factor as an exact decimal so ties are detected reliably
+        let minor = match parse_decimal(&factor.to_string()) {
+            Ok((mantissa, exponent)) if exponent <= 0 && exponent > -30 => {
+                let product = (self.minor_units as i128)
+                    .checked_mul(mantissa)
+                    .ok_or_else(too_large)?;
+                mode.divide(product, 10i128.pow((-exponent) as u32))
+            }
+            _ => {
+                let product = (self.minor_units as f64 * factor).round();
+                // The bounds are powers of two, so the comparison is exact
+                if !(product >= i64::MIN as f64 && product < i64::MAX as f64) {
+                    return Err(too_large());
+                }
+                product as i128
+            }
+        };
+        let minor_units = i64::try_from(minor).map_err(|_| too_large())?;
+        Ok(Money::from_minor_units(minor_units, self.currency))
+    }
+
+    /// Divide evenly by an integer count (e.g. per-show or per-ticket averages)
+    pub fn divide(&self, divisor: i64, mode: RoundingMode) -> Money {
+        if divisor == 0 {
+            return Money::zero(self.currency);
+        }
+        let (numerator, denominator) = if divisor < 0 {
+            (-(self.minor_units as i128), -(divisor as i128))
+        } else {
+            (self.minor_units as i128, divisor as i128)
+        };
+        Money::from_minor_units(mode.divide(numerator, denominator) as i64, self.currency)
+    }
+
+    /// Sum float amounts, converting each to minor units first so totals do not drift
+    pub fn sum_f64<I: IntoIterator<Item = f64>>(
+        amounts: I,
+        currency: Currency,
+        mode: RoundingMode,
+    ) -> Money {
+        let minor_units = amounts
+            .into_iter()
+            .map(|a| Money::from_f64(a, currency, mode).minor_units)
+            .fold(0i64, |acc, v| acc.saturating_add(v));
+        Money::from_minor_units(minor_units, currency)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let decimals = self.currency.decimals() as usize;
+        let scale = self.currency.scale() as i64;
+        let sign = if self.minor_units < 0 { "-" } else { "" };
+        let abs = self.minor_units.unsigned_abs();
+        if decimals == 0 {
+            write!(f, "{}{} {}", sign, abs, self.currency)
+        } else {
+            write!(
+                f,
+                "{}{}.{:0width$} {}",
+                sign,
+                abs / scale as u64,
+                abs % scale as u64,
+                self.currency,
+                width = decimals
+            )
+        }
+    }
+}
+
+/// Split a decimal literal into an integer mantissa and base-10 exponent
+fn parse_decimal(value: &str) -> Result<(i128, i32), String> {
+    let invalid = || format!("Invalid decimal amount '{}'", value);
+    let trimmed = value.trim();
+    let (number, exponent) = match trimmed.find(['e', 'E']) {
+        Some(i) => (
+            &trimmed[..i],
+            trimmed[i + 1..].parse::<i32>().map_err(|_| invalid())?,
+        ),
+        None => (trimmed, 0),
+    };
+    let (negative, digits) = match number.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, number.strip_prefix('+').unwrap_or(number)),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if (whole.is_empty() && fraction.is_empty())
+        || !whole.bytes().all(|b| b.is_ascii_digit())
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+        || whole.len() + fraction.len() > 30
+    {
+        return Err(invalid());
+    }
+
+    let mut mantissa: i128 = 0;
+    for b in whole.bytes().chain(fraction.bytes()) {
+        mantissa = mantissa * 10 + (b - b'0') as i128;
+    }
+    if negative {
+        mantissa = -mantissa;
+    }
+    let exponent = exponent
+        .checked_sub(fraction.len() as i32)
+        .ok_or_else(invalid)?;
+    Ok((mantissa, exponent))
+}
+
+/// `(mantissa, exponent)` with trailing zeros moved into the exponent, so equal values compare
+/// equal however they were written
+fn normalize((mut mantissa, mut exponent): (i128, i32)) -> (i128, i32) {
+    if mantissa == 0 {
+        return (0, 0);
+    }
+    while mantissa % 10 == 0 {
+        mantissa /= 10;
+        exponent += 1;
+    }
+    (mantissa, exponent)
+}
+
+/// Serde helper for monetary fields: accepts JSON numbers or decimal strings. Amounts are
+/// turned back into exact decimals by `Money::from_f64`, so a string is rejected unless its
+/// value survives that round trip.
+pub fn deserialize_amount<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    struct AmountVisitor;
+
+    impl Visitor<'_> for AmountVisitor {
+        type Value = f64;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a number or a decimal string")
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<f64, E> {
+            Ok(v)
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<f64, E> {
+            Ok(v as f64)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<f64, E> {
+            Ok(v as f64)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<f64, E> {
+            let decimal = parse_decimal(v).map_err(E::custom)?;
+            let amount = v.trim().parse::<f64>().map_err(E::custom)?;
+            let exact = amount.is_finite()
+                && parse_decimal(&amount.to_string()).map(normalize) == Ok(normalize(decimal));
+            if exact {
+                Ok(amount)
+            } else {
+                Err(E::custom(format!(
+                    "Amount '{}' has more digits than can be kept exactly",
+                    v
+                )))
+            }
+        }
+    }
+
+    deserializer.deserialize_any(AmountVisitor)
+}
+
+/// Serde helper for optional monetary fields; pair with `#[serde(default)]` so a missing
+/// field is `None` as well as `null`
+pub fn deserialize_opt_amount<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<f64>, D::Error> {
+    #[derive(Deserialize)]
+    struct Amount(#[serde(deserialize_with = "deserialize_amount")] f64);
+
+    Ok(Option::<Amount>::deserialize(deserializer)?.map(|Amount(amount)| amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eur(value: &str) -> Money {
+        Money::parse(value, DEFAULT_CURRENCY, RoundingMode::HalfEven).unwrap()
+    }
+
+    #[test]
+    fn ties_round_by_mode() {
+        let half_even = |v| Money::parse(v, DEFAULT_CURRENCY, RoundingMode::HalfEven);
+        let half_up = |v| Money::parse(v, DEFAULT_CURRENCY, RoundingMode::HalfUp);
+        assert_eq!(half_even("2.345").unwrap().minor_units(), 234);
+        assert_eq!(half_even("2.355").unwrap().minor_units(), 236);
+        assert_eq!(half_up("2.345").unwrap().minor_units(), 235);
+        assert_eq!(half_up("-2.345").unwrap().minor_units(), -235);
+        assert_eq!(half_even("-2.345").unwrap().minor_units(), -234);
+        assert_eq!(half_even("2.3451").unwrap().minor_units(), 235);
+    }
+
+    #[test]
+    fn parse_handles_exponents_and_minor_units() {
+        assert_eq!(eur("1.5e3").minor_units(), 150000);
+        assert_eq!(eur("+12").minor_units(), 1200);
+        assert_eq!(eur(".5").minor_units(), 50);
+        let yen = Money::parse(
+            "1234.5",
+            Currency::parse("jpy").unwrap(),
+            RoundingMode::HalfUp,
+        );
+        assert_eq!(yen.unwrap().minor_units(), 1235);
+        let dinar = Money::parse(
+            "1.2345",
+            Currency::parse("KWD").unwrap(),
+            RoundingMode::HalfEven,
+        );
+        assert_eq!(dinar.unwrap().minor_units(), 1234);
+    }
+
+    #[test]
+    fn parse_rejects_bad_and_out_of_range_input() {
+        let parse = |v| Money::parse(v, DEFAULT_CURRENCY, RoundingMode::HalfEven);
+        for value in [
+            "",
+            ".",
+            "1.2.3",
+            "12a",
+            "1e",
+            "--1",
+            "1e40",
+            "1e-50",
+            "1e2147483647",
+        ] {
+            assert!(parse(value).is_err(), "{}", value);
+        }
+        assert!(parse("92233720368547758.08").is_err());
+    }
+
+    #[test]
+    fn floats_convert_through_their_shortest_decimal() {
+        let mode = RoundingMode::HalfEven;
+        assert_eq!(
+            Money::from_f64(0.1, DEFAULT_CURRENCY, mode).minor_units(),
+            10
+        );
+        assert_eq!(
+            Money::from_f64(1.005, DEFAULT_CURRENCY, mode).minor_units(),
+            100
+        );
+        assert_eq!(
+            Money::from_f64(f64::NAN, DEFAULT_CURRENCY, mode).minor_units(),
+            0
+        );
+        let total = Money::sum_f64([0.1; 10], DEFAULT_CURRENCY, mode);
+        assert_eq!(total, eur("1.00"));
+    }
+
+    #[test]
+    fn arithmetic_rounds_and_checks_currency() {
+        let mode = RoundingMode::HalfEven;
+        assert_eq!(eur("10.00").multiply(0.125, mode), Ok(eur("1.25")));
+        assert_eq!(eur("0.10").multiply(0.25, mode), Ok(eur("0.02")));
+        assert_eq!(eur("10.00").divide(3, mode), eur("3.33"));
+        assert_eq!(eur("10.00").divide(-4, mode), eur("-2.50"));
+        assert_eq!(eur("10.00").divide(0, mode), eur("0"));
+        assert_eq!(eur("2.50").times(3), Ok(eur("7.50")));
+        let usd = Money::from_minor_units(100, Currency::parse("USD").unwrap());
+        assert!(eur("1.00").checked_add(&usd).is_err());
+        assert_eq!(eur("-1234.5").to_string(), "-1234.50 EUR");
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        let mode = RoundingMode::HalfEven;
+        let max = Money::from_minor_units(i64::MAX, Currency::default());
+        let min = Money::from_minor_units(i64::MIN, Currency::default());
+        assert!(max.checked_add(&eur("0.01")).is_err());
+        assert!(min.checked_sub(&eur("0.01")).is_err());
+        assert!(max.times(2).is_err());
+        assert!(max.multiply(1.5, mode).is_err());
+        assert!(eur("1.00").multiply(1e300, mode).is_err());
+        assert!(eur("1.00").multiply(f64::NAN, mode).is_err());
+        assert_eq!(max.checked_sub(&max), Ok(eur("0")));
+    }
+
+    #[test]
+    fn amounts_deserialize_from_numbers_and_exact_strings() {
+        #[derive(Deserialize)]
+        struct Line {
+            #[serde(deserialize_with = "deserialize_amount")]
+            amount: f64,
+            #[serde(default, deserialize_with = "deserialize_opt_amount")]
+            cap: Option<f64>,
+        }
+        let parse = |json: &str| serde_json::from_str::<Line>(json);
+        let line = parse(r#"{ "amount": "1234.50", "cap": "0.1" }"#).unwrap();
+        assert_eq!((line.amount, line.cap), (1234.5, Some(0.1)));
+        let line = parse(r#"{ "amount": 7 }"#).unwrap();
+        assert_eq!((line.amount, line.cap), (7.0, None));
+        assert!(parse(r#"{ "amount": "12.3.4" }"#).is_err());
+        assert!(parse(r#"{ "amount": "0.12345678901234567890" }"#).is_err());
+        assert!(parse(r#"{ "amount": "1e400" }"#).is_err());
+    }
+}
@@ -0,0 +1,473 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Show;
+
+pub(crate) const DEFAULT_SEED: u64 = 0x5EED_F00D_CAFE_BABE;
+
+/// Most iterations one simulation runs; every outcome is kept for the percentiles
+const MAX_ITERATIONS: u32 = 100_000;
+
+/// Most bars in the outcome histogram
+const MAX_HISTOGRAM_BINS: usize = 1000;
+
+fn default_histogram_bins() -> usize {
+    20
+}
+
+/// Small deterministic PRNG (SplitMix64) so simulations are reproducible from a seed
+#[derive(Clone)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform sample in [0, 1)
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal sample (Box-Muller)
+    pub(crate) fn standard_normal(&mut self) -> f64 {
+        let u1 = 1.0 - self.next_f64(); // (0, 1] so ln() stays finite
+        let u2 = self.next_f64();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+
+    /// Gamma sample with unit scale for `shape >= 1` (Marsaglia-Tsang)
+    pub(crate) fn gamma(&mut self, shape: f64) -> f64 {
+        let d = shape - 1.0 / 3.0;
+        let c = 1.0 / (9.0 * d).sqrt();
+        loop {
+            let x = self.standard_normal();
+            let v = (1.0 + c * x).powi(3);
+            if v <= 0.0 {
+                continue;
+            }
+            let u = 1.0 - self.next_f64();
+            if u.ln() < 0.5 * x * x + d - d * v + d * v.ln() {
+                return d * v;
+            }
+        }
+    }
+}
+
+/// Probability distribution of a multiplier applied to a show's baseline value, or of a
+/// task's hours. `Pert` is Beta-PERT, with its mean at `(min + 4 * mode + max) / 6`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Distribution {
+    Fixed { value: f64 },
+    Uniform { min: f64, max: f64 },
+    Normal { mean: f64, std_dev: f64 },
+    Triangular { min: f64, mode: f64, max: f64 },
+    Pert { min: f64, mode: f64, max: f64 },
+}
+
+impl Distribution {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        let valid = match *self {
+            Distribution::Fixed { value } => value.is_finite(),
+            Distribution::Uniform { min, max } => min.is_finite() && max.is_finite() && min <= max,
+            Distribution::Normal { mean, std_dev } => {
+                mean.is_finite() && std_dev.is_finite() && std_dev >= 0.0
+            }
+            Distribution::Triangular { min, mode, max } | Distribution::Pert { min, mode, max } => {
+                min.is_finite() && max.is_finite() && min <= mode && mode <= max
+            }
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("Invalid distribution parameters: {:?}", self))
+        }
+    }
+
+    pub(crate) fn sample(&self, rng: &mut Rng) -> f64 {
+        match *self {
+            Distribution::Fixed { value } => value,
+            Distribution::Uniform { min, max } => min + (max - min) * rng.next_f64(),
+            Distribution::Normal { mean, std_dev } => mean + std_dev * rng.standard_normal(),
+            Distribution::Triangular { min, mode, max } => {
+                if max <= min {
+                    return min;
+                }
+                let u = rng.next_f64();
+                let split = (mode - min) / (max - min);
+                if u < split {
+                    min + (u * (max - min) * (mode - min)).sqrt()
+                } else {
+                    max - ((1.0 - u) * (max - min) * (max - mode)).sqrt()
+                }
+            }
+            Distribution::Pert { min, mode, max } => {
+                if max <= min {
+                    return min;
+                }
+                let alpha = 1.0 + 4.0 * (mode - min) / (max - min);
+                let beta = 1.0 + 4.0 * (max - mode) / (max - min);
+                let x = rng.gamma(alpha);
+                let y = rng.gamma(beta);
+                min + (max - min) * x / (x + y)
+            }
+        }
+    }
+}
+
+/// Distribution overrides for a single show, addressed by its index in the loaded set
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ShowDistributions {
+    pub show_index: usize,
+    #[serde(default)]
+    pub attendance: Option<Distribution>,
+    #[serde(default)]
+    pub ticket_price: Option<Distribution>,
+    #[serde(default)]
+    pub expenses: Option<Distribution>,
+}
+
+/// Monte Carlo configuration. Drivers without an explicit distribution fall back
+/// to `Normal { mean: 1.0, std_dev: volatility }`.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SimulationOptions {
+    pub iterations: u32,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub volatility: f64,
+    #[serde(default = "default_histogram_bins")]
+    pub histogram_bins: usize,
+    #[serde(default)]
+    pub attendance: Option<Distribution>,
+    #[serde(default)]
+    pub ticket_price: Option<Distribution>,
+    #[serde(default)]
+    pub expenses: Option<Distribution>,
+    #[serde(default)]
+    pub per_show: Vec<ShowDistributions>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HistogramBin {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SimulationResult {
+    pub iterations: u32,
+    pub seed: u64,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub variance: f64,
+    pub min: f64,
+    pub max: f64,
+    pub p10: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub probability_of_loss: f64,
+    pub success_rate: f64,
+    pub histogram: Vec<HistogramBin>,
+    pub time: f64,
+}
+
+struct ShowDrivers {
+    attendance: Distribution,
+    ticket_price: Distribution,
+    expenses: Distribution,
+}
+
+/// Linear-interpolated percentile of an already sorted sample (`p` in 0..=100)
+pub(crate) fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (p / 100.0).clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - lower as f64;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * weight
+}
+
+fn histogram(sorted: &[f64], bins: usize) -> Vec<HistogramBin> {
+    let (min, max) = match (sorted.first(), sorted.last()) {
+        (Some(&min), Some(&max)) => (min, max),
+        _ => return Vec::new(),
+    };
+    if max <= min {
+        return vec![HistogramBin {
+            lower: min,
+            upper: max,
+            count: sorted.len() as u32,
+        }];
+    }
+
+    let bins = bins.max(1);
+    let width = (max - min) / bins as f64;
+    let mut result: Vec<HistogramBin> = (0..bins)
+        .map(|i| HistogramBin {
+            lower: min + width * i as f64,
+            upper: min + width * (i + 1) as f64,
+            count: 0,
+        })
+        .collect();
+    for &value in sorted {
+        let index = (((value - min) / width) as usize).min(bins - 1);
+        result[index].count += 1;
+    }
+    result
+}
+
+/// Simulate net profit across `shows`, sampling attendance, ticket price and expenses per show
+pub fn simulate(shows: &[Show], options: &SimulationOptions) -> Result<SimulationResult, String> {
+    if !(1..=MAX_ITERATIONS).contains(&options.iterations) {
+        return Err(format!(
+            "iterations must be between 1 and {}",
+            MAX_ITERATIONS
+        ));
+    }
+    if options.histogram_bins > MAX_HISTOGRAM_BINS {
+        return Err(format!(
+            "histogram_bins must be at most {}",
+            MAX_HISTOGRAM_BINS
+        ));
+    }
+    if !options.volatility.is_finite() || options.volatility < 0.0 {
+        return Err("volatility must be a non-negative number".to_string());
+    }
+
+    let fallback = Distribution::Normal {
+        mean: 1.0,
+        std_dev: options.volatility,
+    };
+    let mut drivers: Vec<ShowDrivers> = shows
+        .iter()
+        .map(|_| ShowDrivers {
+            attendance: options.attendance.unwrap_or(fallback),
+            ticket_price: options.ticket_price.unwrap_or(fallback),
+            expenses: options.expenses.unwrap_or(fallback),
+        })
+        .collect();
+    for over in &options.per_show {
+        let target = drivers
+            .get_mut(over.show_index)
+            .ok_or_else(|| format!("per_show index {} is out of range", over.show_index))?;
+        if let Some(d) = over.attendance {
+            target.attendance = d;
+        }
+        if let Some(d) = over.ticket_price {
+            target.ticket_price = d;
+        }
+        if let Some(d) = over.expenses {
+            target.expenses = d;
+        }
+    }
+    for d in &drivers {
+        d.attendance.validate()?;
+        d.ticket_price.validate()?;
+        d.expenses.validate()?;
+    }
+
+    let seed = options.seed.unwrap_or(DEFAULT_SEED);
+    let mut rng = Rng::new(seed);
+    let mut outcomes = Vec::with_capacity(options.iterations as usize);
+
+    for _ in 0..options.iterations {
+        let mut net = 0.0;
+        for (show, d) in shows.iter().zip(&drivers) {
+            let attendance = d.attendance.sample(&mut rng).max(0.0);
+            let price = d.ticket_price.sample(&mut rng).max(0.0);
+            let expense = d.expenses.sample(&mut rng).max(0.0);
+
+            let revenue = if show.tickets_sold > 0 {
+                let base_price = show.revenue / show.tickets_sold as f64;
+                let mut tickets = show.tickets_sold as f64 * attendance;
+                if show.capacity > 0 {
+                    tickets = tickets.min(show.capacity as f64);
+                }
+                tickets * base_price * price
+            } else {
+                show.revenue * attendance * price
+            };
+            net += revenue - show.expenses * expense;
+        }
+        outcomes.push(net);
+    }
+
+    outcomes.sort_by(|a, b| a.total_cmp(b));
+    let n = outcomes.len() as f64;
+    let mean = outcomes.iter().sum::<f64>() / n;
+    let variance = outcomes.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    let losses = outcomes.iter().filter(|&&v| v < 0.0).count() as f64;
+    let probability_of_loss = losses / n;
+
+    Ok(SimulationResult {
+        iterations: options.iterations,
+        seed,
+        mean,
+        std_dev: variance.sqrt(),
+        variance,
+        min: outcomes[0],
+        max: outcomes[outcomes.len() - 1],
+        p10: percentile(&outcomes, 10.0),
+        p50: percentile(&outcomes, 50.0),
+        p90: percentile(&outcomes, 90.0),
+        probability_of_loss,
+        success_rate: (1.0 - probability_of_loss) * 100.0,
+        histogram: histogram(&outcomes, options.histogram_bins),
+        time: 0.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn shows() -> Vec<Show> {
+        serde_json::from_value(json!([
+            { "date": "2025-06-01", "revenue": 20000, "expenses": 12000, "capacity": 1000, "tickets_sold": 800 },
+            { "date": "2025-06-02", "revenue": 5000, "expenses": 1000 }
+        ]))
+        .unwrap()
+    }
+
+    fn options(value: serde_json::Value) -> SimulationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn mean_of(distribution: Distribution, n: usize) -> f64 {
+        let mut rng = Rng::new(42);
+        (0..n).map(|_| distribution.sample(&mut rng)).sum::<f64>() / n as f64
+    }
+
+    #[test]
+    fn samples_match_their_distribution() {
+        let uniform = Distribution::Uniform { min: 2.0, max: 4.0 };
+        assert!((mean_of(uniform, 20000) - 3.0).abs() < 0.02);
+        let normal = Distribution::Normal {
+            mean: 1.0,
+            std_dev: 0.2,
+        };
+        assert!((mean_of(normal, 20000) - 1.0).abs() < 0.01);
+        let triangular = Distribution::Triangular {
+            min: 0.0,
+            mode: 3.0,
+            max: 6.0,
+        };
+        assert!((mean_of(triangular, 20000) - 3.0).abs() < 0.05);
+        let pert = Distribution::Pert {
+            min: 10.0,
+            mode: 20.0,
+            max: 60.0,
+        };
+        assert!((mean_of(pert, 20000) - 25.0).abs() < 0.3);
+
+        let mut rng = Rng::new(7);
+        assert!((0..1000).all(|_| {
+            let x = pert.sample(&mut rng);
+            (10.0..=60.0).contains(&x)
+        }));
+    }
+
+    #[test]
+    fn invalid_distributions_are_rejected() {
+        assert!(Distribution::Uniform { min: 2.0, max: 1.0 }
+            .validate()
+            .is_err());
+        assert!(Distribution::Normal {
+            mean: 1.0,
+            std_dev: -1.0
+        }
+        .validate()
+        .is_err());
+        assert!(Distribution::Pert {
+            min: 0.0,
+            mode: 5.0,
+            max: 4.0
+        }
+        .validate()
+        .is_err());
+        assert!(Distribution::Fixed { value: f64::NAN }.validate().is_err());
+    }
+
+    #[test]
+    fn percentiles_interpolate() {
+        let sorted = [10.0, 20.0, 30.0, 40.0, 50.0];
+        assert_eq!(percentile(&sorted, 50.0), 30.0);
+        assert_eq!(percentile(&sorted, 10.0), 14.0);
+        assert_eq!(percentile(&sorted, 100.0), 50.0);
+        assert_eq!(percentile(&[], 50.0), 0.0);
+    }
+
+    #[test]
+    fn fixed_drivers_reproduce_the_baseline() {
+        let result = simulate(&shows(), &options(json!({ "iterations": 10 }))).unwrap();
+        assert_eq!(
+            (result.min, result.max, result.mean),
+            (12000.0, 12000.0, 12000.0)
+        );
+        assert_eq!(result.probability_of_loss, 0.0);
+        assert_eq!(result.histogram.len(), 1);
+        assert_eq!(result.histogram[0].count, 10);
+    }
+
+    #[test]
+    fn attendance_is_capped_by_capacity() {
+        let result = simulate(
+            &shows(),
+            &options(json!({
+                "iterations": 5,
+                "per_show": [{ "show_index": 0, "attendance": { "type": "fixed", "value": 2 } }]
+            })),
+        )
+        .unwrap();
+        // 1000 tickets at 25 instead of 800, the untiered fee unchanged
+        assert_eq!(result.mean, 17000.0);
+    }
+
+    #[test]
+    fn seeded_runs_are_reproducible() {
+        let options = options(
+            json!({ "iterations": 500, "volatility": 0.3, "seed": 11, "histogram_bins": 8 }),
+        );
+        let first = simulate(&shows(), &options).unwrap();
+        let second = simulate(&shows(), &options).unwrap();
+        assert_eq!(first.p50, second.p50);
+        assert!(first.p10 < first.p50 && first.p50 < first.p90);
+        assert!(first.probability_of_loss > 0.0);
+        assert_eq!(first.histogram.iter().map(|b| b.count).sum::<u32>(), 500);
+        assert_eq!(first.histogram.len(), 8);
+    }
+
+    #[test]
+    fn bad_options_are_rejected() {
+        let shows = shows();
+        assert!(simulate(&shows, &options(json!({ "iterations": 0 }))).is_err());
+        assert!(simulate(&shows, &options(json!({ "iterations": 4_000_000_000u32 }))).is_err());
+        assert!(simulate(
+            &shows,
+            &options(json!({ "iterations": 1, "histogram_bins": 1_000_000 }))
+        )
+        .is_err());
+        assert!(simulate(
+            &shows,
+            &options(json!({ "iterations": 1, "volatility": -1 }))
+        )
+        .is_err());
+        let out_of_range = options(json!({ "iterations": 1, "per_show": [{ "show_index": 5 }] }));
+        assert!(simulate(&shows, &out_of_range).is_err());
+    }
+}
@@ -0,0 +1,459 @@
+use serde::{Deserialize, Serialize};
+
+use crate::date::Date;
+use crate::monte_carlo::percentile;
+use crate::Show;
+
+fn default_behind_threshold() -> f64 {
+    10.0
+}
+
+fn default_interval() -> f64 {
+    80.0
+}
+
+/// Cumulative tickets sold by the end of `date`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SalesPoint {
+    pub date: String,
+    pub tickets: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SalesCurve {
+    /// Loaded show the curve belongs to, giving its date and capacity
+    pub show_index: usize,
+    pub sales: Vec<SalesPoint>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PaceOptions {
+    /// On-sale shows to track
+    pub shows: Vec<SalesCurve>,
+    /// Curves of past comparable shows; each ends at its show's `tickets_sold`
+    pub comparables: Vec<SalesCurve>,
+    /// Date sales are counted up to; each show's latest sales point when absent
+    #[serde(default)]
+    pub as_of: Option<String>,
+    /// Percentage points of capacity a show may trail its comparables before it needs marketing
+    #[serde(default = "default_behind_threshold")]
+    pub behind_threshold: f64,
+    /// Width (0-100) of the prediction intervals, e.g. 80 for 10th to 90th percentile
+    #[serde(default = "default_interval")]
+    pub interval: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PaceStatus {
+    Ahead,
+    OnPace,
+    Behind,
+    SoldOut,
+    /// No comparable with a known capacity was on sale this far out
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PacePoint {
+    pub date: String,
+    pub days_out: i64,
+    pub tickets: u32,
+    /// Tickets the comparables' median sell-through at this point would give
+    pub expected_tickets: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ShowPace {
+    pub show_index: usize,
+    pub date: String,
+    pub capacity: u32,
+    pub as_of: String,
+    pub days_out: i64,
+    pub tickets_sold: u32,
+    /// Sold share (0-100) of capacity
+    pub sell_through: Option<f64>,
+    /// Median sell-through of the comparables at the same distance from their show
+    pub expected_sell_through: Option<f64>,
+    /// Sell-through minus expected sell-through, in percentage points
+    pub pace_gap: Option<f64>,
+    pub status: PaceStatus,
+    pub needs_marketing: bool,
+    /// Comparables already on sale this far out
+    pub comparables_used: usize,
+    pub predicted_final: Option<u32>,
+    pub final_low: Option<u32>,
+    pub final_high: Option<u32>,
+    /// Share (0-1) of comparable trajectories that sell out before the show
+    pub sell_out_probability: f64,
+    pub predicted_sell_out: Option<String>,
+    pub sell_out_earliest: Option<String>,
+    /// `None` when the later end of the interval does not sell out
+    pub sell_out_latest: Option<String>,
+    pub curve: Vec<PacePoint>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PaceReport {
+    pub interval: f64,
+    pub shows: Vec<ShowPace>,
+}
+
+/// A past show's sales by days before the show, furthest out first
+struct Comparable {
+    points: Vec<(i64, f64)>,
+    final_tickets: f64,
+    capacity: u32,
+}
+
+impl Comparable {
+    /// Tickets sold `days_out` days before the show, interpolated between sales points;
+    /// `None` before the first point
+    fn tickets_at(&self, days_out: i64) -> Option<f64> {
+        let days_out = days_out.max(0);
+        let first = self.points.first()?;
+        if days_out > first.0 {
+            return None;
+        }
+        let after = self.points.iter().position(|&(d, _)| d <= days_out)?;
+        let (d1, t1) = self.points[after];
+        if after == 0 || d1 == days_out {
+            return Some(t1);
+        }
+        let (d0, t0) = self.points[after - 1];
+        Some(t0 + (t1 - t0) * (d0 - days_out) as f64 / (d0 - d1) as f64)
+    }
+
+    /// Share (0-1) of final tickets sold `days_out` days before the show
+    fn share_at(&self, days_out: i64) -> Option<f64> {
+        self.tickets_at(days_out)
+            .map(|t| (t / self.final_tickets).min(1.0))
+    }
+
+    fn sell_through_at(&self, days_out: i64) -> Option<f64> {
+        if self.capacity == 0 {
+            return None;
+        }
+        self.tickets_at(days_out)
+            .map(|t| t / self.capacity as f64 * 100.0)
+    }
+}
+
+fn show_at(shows: &[Show], index: usize) -> Result<&Show, String> {
+    shows
+        .get(index)
+        .ok_or_else(|| format!("Show index {} out of range", index))
+}
+
+/// Sales points parsed and sorted by date
+fn parse_sales(curve: &SalesCurve) -> Result<Vec<(Date, u32)>, String> {
+    let mut points = curve
+        .sales
+        .iter()
+        .map(|p| Date::parse(&p.date).map(|d| (d, p.tickets)))
+        .collect::<Result<Vec<_>, String>>()?;
+    points.sort_by_key(|&(date, _)| date);
+    Ok(points)
+}
+
+fn comparable(shows: &[Show], curve: &SalesCurve) -> Result<Option<Comparable>, String> {
+    let show = show_at(shows, curve.show_index)?;
+    let show_date = Date::parse(&show.date)?;
+    let sales = parse_sales(curve)?;
+    let mut points: Vec<(i64, f64)> = sales
+        .iter()
+        .map(|&(date, tickets)| (date.days_until(&show_date), tickets as f64))
+        .filter(|&(days_out, _)| days_out >= 0)
+        .collect();
+    let final_tickets = points
+        .iter()
+        .map(|&(_, t)| t)
+        .fold(show.tickets_sold as f64, f64::max);
+    if final_tickets <= 0.0 {
+        return Ok(None);
+    }
+    if points.last().is_none_or(|&(days_out, _)| days_out > 0) {
+        points.push((0, final_tickets));
+    }
+    Ok(Some(Comparable {
+        points,
+        final_tickets,
+        capacity: show.capacity,
+    }))
+}
+
+/// Value at percentile `p` (0-100) by nearest rank, for lists where `None` sorts last
+fn nearest_rank<T: Copy>(sorted: &[T], p: f64) -> Option<T> {
+    let rank = ((p / 100.0).clamp(0.0, 1.0) * (sorted.len() as f64 - 1.0)).round();
+    sorted.get(rank as usize).copied()
+}
+
+/// Compare each on-sale show's sales with the pace of past comparable shows, projecting final
+/// attendance and the sell-out date along every comparable's remaining curve
+pub fn track(shows: &[Show], options: &PaceOptions) -> Result<PaceReport, String> {
+    if !(options.interval > 0.0 && options.interval <= 100.0) {
+        return Err("interval must be greater than 0 and at most 100".to_string());
+    }
+    if options.behind_threshold < 0.0 {
+        return Err("behind_threshold cannot be negative".to_string());
+    }
+    let as_of = options.as_of.as_deref().map(Date::parse).transpose()?;
+    let mut comparables = Vec::with_capacity(options.comparables.len());
+    for curve in &options.comparables {
+        comparables.extend(comparable(shows, curve)?);
+    }
+    let low_p = (100.0 - options.interval) / 2.0;
+    let high_p = 100.0 - low_p;
+
+    let mut results = Vec::with_capacity(options.shows.len());
+    for curve in &options.shows {
+        let show = show_at(shows, curve.show_index)?;
+        let show_date = Date::parse(&show.date)?;
+        let sales: Vec<(Date, u32)> = parse_sales(curve)?
+            .into_iter()
+            .filter(|&(date, _)| as_of.is_none_or(|as_of| date <= as_of))
+            .collect();
+        let &(last_date, tickets) = sales
+            .last()
+            .ok_or_else(|| format!("Show {} has no sales to track", curve.show_index))?;
+        let counted_to = as_of.unwrap_or(last_date);
+        let days_out = counted_to.days_until(&show_date).max(0);
+        let capacity = show.capacity;
+        let sold_out = capacity > 0 && tickets >= capacity;
+
+        let usable: Vec<&Comparable> = comparables
+            .iter()
+            .filter(|c| c.share_at(days_out).is_some_and(|s| s > 0.0))
+            .collect();
+
+        // Median sell-through of comparables at a given distance from their show
+        let expected_at = |days: i64| {
+            let mut values: Vec<f64> = comparables
+                .iter()
+                .filter_map(|c| c.sell_through_at(days))
+                .collect();
+            values.sort_by(|a, b| a.total_cmp(b));
+            (!values.is_empty()).then(|| percentile(&values, 50.0))
+        };
+
+        let sell_through = (capacity > 0).then(|| tickets as f64 / capacity as f64 * 100.0);
+        let expected_sell_through = if capacity > 0 {
+            expected_at(days_out)
+        } else {
+            None
+        };
+        let pace_gap = sell_through
+            .zip(expected_sell_through)
+            .map(|(actual, expected)| actual - expected);
+        let status = match pace_gap {
+            _ if sold_out => PaceStatus::SoldOut,
+            Some(gap) if gap < -options.behind_threshold => PaceStatus::Behind,
+            Some(gap) if gap > options.behind_threshold => PaceStatus::Ahead,
+            Some(_) => PaceStatus::OnPace,
+            None => PaceStatus::Unknown,
+        };
+
+        let mut finals: Vec<f64> = usable
+            .iter()
+            .filter_map(|c| c.share_at(days_out))
+            .map(|share| {
+                let projected = (tickets as f64 / share).floor();
+                if capacity > 0 {
+                    projected.min(capacity as f64)
+                } else {
+                    projected
+                }
+            })
+            .collect();
+        finals.sort_by(|a, b| a.total_cmp(b));
+        let final_at = |p: f64| (!finals.is_empty()).then(|| percentile(&finals, p).round() as u32);
+
+        // Days before the show each comparable's trajectory reaches capacity; `None` never
+        let mut sell_outs: Vec<Option<i64>> = if sold_out {
+            let reached = sales
+                .iter()
+                .find(|&&(_, t)| t >= capacity)
+                .map_or(counted_to, |&(date, _)| date);
+            vec![Some(reached.days_until(&show_date))]
+        } else if capacity > 0 {
+            usable
+                .iter()
+                .map(|c| {
+                    let share = c.share_at(days_out).unwrap_or(1.0);
+                    (0..days_out).rev().find(|&d| {
+                        c.share_at(d)
+                            .is_some_and(|s| tickets as f64 * s / share >= capacity as f64)
+                    })
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        // Earliest date first, never selling out last
+        sell_outs.sort_by_key(|d| d.map_or(i64::MAX, |d| -d));
+        let sell_out_probability = if sell_outs.is_empty() {
+            0.0
+        } else {
+            sell_outs.iter().filter(|d| d.is_some()).count() as f64 / sell_outs.len() as f64
+        };
+        let sell_out_date = |p: f64| {
+            nearest_rank(&sell_outs, p)
+                .flatten()
+                .map(|d| show_date.add_days(-d).to_string())
+        };
+
+        let points = sales
+            .iter()
+            .map(|&(date, t)| {
+                let days = date.days_until(&show_date);
+                PacePoint {
+                    date: date.to_string(),
+                    days_out: days,
+                    tickets: t,
+                    expected_tickets: if capacity > 0 {
+                        expected_at(days).map(|e| (e / 100.0 * capacity as f64).round() as u32)
+                    } else {
+                        None
+                    },
+                }
+            })
+            .collect();
+
+        results.push(ShowPace {
+            show_index: curve.show_index,
+            date: show.date.clone(),
+            capacity,
+            as_of: counted_to.to_string(),
+            days_out,
+            tickets_sold: tickets,
+            sell_through,
+            expected_sell_through,
+            pace_gap,
+            status,
+            needs_marketing: status == PaceStatus::Behind,
+            comparables_used: usable.len(),
+            predicted_final: if sold_out {
+                Some(tickets)
+            } else {
+                final_at(50.0)
+            },
+            final_low: if sold_out {
+                Some(tickets)
+            } else {
+                final_at(low_p)
+            },
+            final_high: if sold_out {
+                Some(tickets)
+            } else {
+                final_at(high_p)
+            },
+            sell_out_probability,
+            predicted_sell_out: sell_out_date(50.0),
+            sell_out_earliest: sell_out_date(low_p),
+            sell_out_latest: sell_out_date(high_p),
+            curve: points,
+        });
+    }
+
+    Ok(PaceReport {
+        interval: options.interval,
+        shows: results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn shows() -> Vec<Show> {
+        [
+            ("2025-03-01", 1000),
+            ("2025-04-01", 800),
+            ("2025-09-01", 0),
+            ("2025-10-01", 0),
+            ("2025-11-01", 1000),
+        ]
+        .iter()
+        .map(|(date, sold)| {
+            serde_json::from_value(json!({
+                "date": date, "revenue": 0, "expenses": 0, "capacity": 1000, "tickets_sold": sold
+            }))
+            .unwrap()
+        })
+        .collect()
+    }
+
+    fn options(shows: serde_json::Value) -> PaceOptions {
+        serde_json::from_value(json!({
+            "shows": shows,
+            "comparables": [
+                { "show_index": 0, "sales": [
+                    { "date": "2025-01-30", "tickets": 500 },
+                    { "date": "2025-02-20", "tickets": 900 }
+                ] },
+                { "show_index": 1, "sales": [{ "date": "2025-03-02", "tickets": 400 }] }
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn track_one(sales: serde_json::Value) -> ShowPace {
+        track(&shows(), &options(json!([sales])))
+            .unwrap()
+            .shows
+            .remove(0)
+    }
+
+    #[test]
+    fn trailing_shows_need_marketing() {
+        let pace = track_one(
+            json!({ "show_index": 2, "sales": [{ "date": "2025-08-02", "tickets": 300 }] }),
+        );
+        assert_eq!(pace.days_out, 30);
+        assert_eq!(pace.expected_sell_through, Some(45.0));
+        assert_eq!(pace.pace_gap, Some(-15.0));
+        assert_eq!(pace.status, PaceStatus::Behind);
+        assert!(pace.needs_marketing);
+        assert_eq!(pace.comparables_used, 2);
+        assert_eq!(pace.predicted_final, Some(600));
+        assert_eq!(pace.sell_out_probability, 0.0);
+        assert_eq!(pace.predicted_sell_out, None);
+    }
+
+    #[test]
+    fn sell_out_follows_the_comparables_curves() {
+        let pace = track_one(
+            json!({ "show_index": 3, "sales": [{ "date": "2025-09-01", "tickets": 520 }] }),
+        );
+        assert_eq!(pace.status, PaceStatus::OnPace);
+        assert_eq!(pace.predicted_final, Some(1000));
+        assert_eq!(pace.sell_out_probability, 1.0);
+        assert_eq!(pace.sell_out_earliest.as_deref(), Some("2025-09-28"));
+        assert_eq!(pace.predicted_sell_out.as_deref(), Some("2025-09-29"));
+    }
+
+    #[test]
+    fn sold_out_shows_report_when_they_sold_out() {
+        let pace = track_one(json!({ "show_index": 4, "sales": [
+            { "date": "2025-10-20", "tickets": 1000 },
+            { "date": "2025-10-10", "tickets": 800 }
+        ] }));
+        assert_eq!(pace.status, PaceStatus::SoldOut);
+        assert_eq!(pace.predicted_final, Some(1000));
+        assert_eq!(pace.predicted_sell_out.as_deref(), Some("2025-10-20"));
+        assert_eq!(pace.curve[0].date, "2025-10-10");
+    }
+
+    #[test]
+    fn invalid_requests_are_rejected() {
+        let shows = shows();
+        let empty = options(json!([{ "show_index": 2, "sales": [] }]));
+        assert!(track(&shows, &empty).is_err());
+        let missing = options(json!([{ "show_index": 9, "sales": [] }]));
+        assert!(track(&shows, &missing).is_err());
+        let mut wide = options(json!([]));
+        wide.interval = 0.0;
+        assert!(track(&shows, &wide).is_err());
+    }
+}
@@ -0,0 +1,542 @@
+use serde::{Deserialize, Serialize};
+
+use crate::deal::{DealInputs, DealTerms};
+use crate::demand::{self, DemandCurve, DemandInputs};
+use crate::money::{self, Currency, Money, RoundingMode};
+
+/// Most price tiers a show can be split into
+const MAX_TIERS: usize = 5;
+
+/// Most grid points searched per show
+const MAX_STEPS: usize = 1000;
+
+/// Highest multiple of a show's current price that can be searched
+const MAX_PRICE_RATIO: f64 = 100.0;
+
+/// Gaps between neighbouring tier prices tried for multi-tier plans, as a fraction of the
+/// lower price
+const TIER_SPREADS: [f64; 5] = [0.1, 0.25, 0.5, 0.75, 1.0];
+
+fn default_steps() -> usize {
+    41
+}
+
+fn default_min_ratio() -> f64 {
+    0.5
+}
+
+fn default_max_ratio() -> f64 {
+    2.0
+}
+
+fn default_max_tiers() -> usize {
+    1
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PriceOptimizationOptions {
+    /// Shows to optimize, by index; every loaded show when empty
+    #[serde(default)]
+    pub show_indexes: Vec<usize>,
+    /// Prices searched, as multiples of each show's current price
+    #[serde(default = "default_min_ratio")]
+    pub min_price_ratio: f64,
+    #[serde(default = "default_max_ratio")]
+    pub max_price_ratio: f64,
+    /// Grid points between the lowest and highest price searched
+    #[serde(default = "default_steps")]
+    pub steps: usize,
+    /// Also try splitting capacity into up to this many price tiers
+    #[serde(default = "default_max_tiers")]
+    pub max_tiers: usize,
+    /// No ticket may cost more than this
+    #[serde(default, deserialize_with = "money::deserialize_opt_amount")]
+    pub price_ceiling: Option<f64>,
+    /// Minimum share of capacity (0-100) that must sell
+    #[serde(default)]
+    pub min_sell_through: Option<f64>,
+    /// Fan-accessibility floor: the cheapest tier must cost at most this much...
+    #[serde(default, deserialize_with = "money::deserialize_opt_amount")]
+    pub accessible_price: Option<f64>,
+    /// ...and hold at least this share of capacity (0-100)
+    #[serde(default)]
+    pub accessible_share: f64,
+}
+
+/// One show's figures in the base currency
+pub struct PricingInputs<'a> {
+    pub show_index: usize,
+    pub date: &'a str,
+    /// What the show earns now: its deal payout when it has deal terms
+    pub revenue: f64,
+    pub expenses: f64,
+    /// Candidate ticket grosses are paid out through these terms when present
+    pub deal: Option<&'a DealTerms>,
+    pub promoter_expenses: f64,
+    pub demand: DemandInputs<'a>,
+    pub elasticity: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PricePoint {
+    pub price: f64,
+    pub tickets: u32,
+    pub gross: f64,
+    pub revenue: f64,
+    pub profit: f64,
+    /// Meets the sell-through, ceiling and accessibility constraints
+    pub feasible: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PriceTier {
+    pub price: f64,
+    pub capacity: u32,
+    pub tickets: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PricingPlan {
+    /// Cheapest first
+    pub tiers: Vec<PriceTier>,
+    pub average_price: f64,
+    pub tickets: u32,
+    /// 0-100, or `None` without a known capacity
+    pub sell_through: Option<f64>,
+    /// Ticket sales at face value
+    pub gross: f64,
+    /// What the show earns from `gross`: the deal payout for shows with deal terms
+    pub revenue: f64,
+    pub profit: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ShowPriceOptimization {
+    pub show_index: usize,
+    pub date: String,
+    pub market: Option<String>,
+    pub elasticity: f64,
+    pub current_price: f64,
+    pub current_tickets: u32,
+    pub current_profit: f64,
+    /// Most profitable plan meeting the constraints; `None` when none does or the show has
+    /// no ticket sales to calibrate demand from
+    pub optimal: Option<PricingPlan>,
+    /// Best feasible plan for each tier count tried, single price first
+    pub plans: Vec<PricingPlan>,
+    /// Profit against a single ticket price, over the searched range
+    pub curve: Vec<PricePoint>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PriceOptimizationReport {
+    pub curve: DemandCurve,
+    pub shows: Vec<ShowPriceOptimization>,
+    pub total_current_profit: f64,
+    /// Sum of the optimal plans' profit, with current profit for shows without one
+    pub total_optimal_profit: f64,
+}
+
+impl PriceOptimizationOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(2..=MAX_STEPS).contains(&self.steps) {
+            return Err(format!("steps must be between 2 and {}", MAX_STEPS));
+        }
+        if !(1..=MAX_TIERS).contains(&self.max_tiers) {
+            return Err(format!("max_tiers must be between 1 and {}", MAX_TIERS));
+        }
+        if !(self.min_price_ratio > 0.0
+            && self.min_price_ratio <= self.max_price_ratio
+            && self.max_price_ratio <= MAX_PRICE_RATIO)
+        {
+            return Err(format!(
+                "Price ratios must be positive with min_price_ratio <= max_price_ratio <= {}",
+                MAX_PRICE_RATIO
+            ));
+        }
+        if self.price_ceiling.is_some_and(|c| c <= 0.0) {
+            return Err("price_ceiling must be greater than zero".to_string());
+        }
+        if self.accessible_price.is_some_and(|p| p < 0.0) {
+            return Err("accessible_price cannot be negative".to_string());
+        }
+        let shares = [self.min_sell_through, Some(self.accessible_share)];
+        if shares.iter().flatten().any(|s| !(0.0..=100.0).contains(s)) {
+            return Err("Sell-through and accessible share must be between 0 and 100".to_string());
+        }
+        Ok(())
+    }
+}
+
+struct Optimizer<'a> {
+    options: &'a PriceOptimizationOptions,
+    curve: DemandCurve,
+    currency: Currency,
+    mode: RoundingMode,
+}
+
+impl Optimizer<'_> {
+    fn cents(&self, amount: f64) -> Money {
+        Money::from_f64(amount, self.currency, self.mode)
+    }
+
+    /// Tickets bought at each tier when the fans willing to pay most take the dearest
+    /// tickets first: a tier sells what demand at its price leaves after the tiers above
+    fn evaluate(
+        &self,
+        show: &PricingInputs,
+        prices: &[f64],
+        capacities: &[u32],
+    ) -> Result<PricingPlan, String> {
+        let current = show.demand.ticket_price;
+        let mut sold = vec![0u32; prices.len()];
+        let mut above = 0u32;
+        for j in (0..prices.len()).rev() {
+            let wanted = demand::demand(
+                self.curve,
+                show.elasticity,
+                &show.demand,
+                prices[j] / current,
+            )
+            .max(0.0)
+            .floor() as u32;
+            let tickets = wanted.saturating_sub(above);
+            sold[j] = if show.demand.capacity > 0 {
+                tickets.min(capacities[j])
+            } else {
+                tickets
+            };
+            above += sold[j];
+        }
+
+        let mut gross = Money::zero(self.currency);
+        let mut tiers = Vec::with_capacity(prices.len());
+        for (j, &price) in prices.iter().enumerate() {
+            let price = self.cents(price);
+            gross = gross.checked_add(&price.times(sold[j] as i64)?)?;
+            tiers.push(PriceTier {
+                price: price.to_f64(),
+                capacity: capacities[j],
+                tickets: sold[j],
+            });
+        }
+        let revenue = match show.deal {
+            Some(deal) => {
+                let inputs = DealInputs {
+                    tickets_sold: above,
+                    capacity: show.demand.capacity,
+                    gross_box_office: Some(gross.to_f64()),
+                    promoter_expenses: show.promoter_expenses,
+                };
+                self.cents(deal.payout(&inputs, self.currency, self.mode)?.payout)
+            }
+            None => gross,
+        };
+        let profit = revenue.checked_sub(&self.cents(show.expenses))?;
+        Ok(PricingPlan {
+            tiers,
+            average_price: if above > 0 {
+                gross.divide(above as i64, self.mode).to_f64()
+            } else {
+                0.0
+            },
+            tickets: above,
+            sell_through: (show.demand.capacity > 0)
+                .then(|| above as f64 / show.demand.capacity as f64 * 100.0),
+            gross: gross.to_f64(),
+            revenue: revenue.to_f64(),
+            profit: profit.to_f64(),
+        })
+    }
+
+    fn feasible(&self, plan: &PricingPlan) -> bool {
+        let options = self.options;
+        let sells = match (options.min_sell_through, plan.sell_through) {
+            (Some(min), Some(actual)) => actual + 1e-9 >= min,
+            _ => true,
+        };
+        let top = plan.tiers.last().map_or(0.0, |t| t.price);
+        let cheapest = plan.tiers.first().map_or(0.0, |t| t.price);
+        let under_ceiling = options.price_ceiling.is_none_or(|c| top <= c + 1e-9);
+        let accessible = options
+            .accessible_price
+            .is_none_or(|a| cheapest <= a + 1e-9);
+        sells && under_ceiling && accessible
+    }
+
+    /// Capacity per tier, cheapest first: the cheapest holds at least the accessible share
+    /// and the rest is split evenly, with any remainder going to the cheapest tier
+    fn tier_capacities(&self, capacity: u32, tiers: usize) -> Vec<u32> {
+        if tiers == 1 {
+            return vec![capacity];
+        }
+        let accessible_share = if self.options.accessible_price.is_some() {
+            self.options.accessible_share
+        } else {
+            0.0
+        };
+        let cheapest = ((capacity as f64 * accessible_share / 100.0).ceil() as u32)
+            .max(capacity / tiers as u32)
+            .min(capacity);
+        let upper = (capacity - cheapest) / (tiers as u32 - 1);
+        let mut capacities = vec![upper; tiers];
+        capacities[0] = capacity - upper * (tiers as u32 - 1);
+        capacities
+    }
+
+    fn optimize(&self, show: &PricingInputs) -> Result<ShowPriceOptimization, String> {
+        let options = self.options;
+        let current = show.demand.ticket_price;
+        let mut result = ShowPriceOptimization {
+            show_index: show.show_index,
+            date: show.date.to_string(),
+            market: show.demand.market.map(demand::market_key),
+            elasticity: show.elasticity,
+            current_price: self.cents(current).to_f64(),
+            current_tickets: show.demand.tickets_sold,
+            current_profit: self
+                .cents(show.revenue)
+                .checked_sub(&self.cents(show.expenses))?
+                .to_f64(),
+            optimal: None,
+            plans: Vec::new(),
+            curve: Vec::new(),
+        };
+        if show.demand.tickets_sold == 0 || current <= 0.0 {
+            return Ok(result);
+        }
+
+        let mut high = current * options.max_price_ratio;
+        if let Some(ceiling) = options.price_ceiling {
+            high = high.min(ceiling);
+        }
+        let low = (current * options.min_price_ratio).min(high).max(0.01);
+        let grid: Vec<f64> = (0..options.steps)
+            .map(|s| low + (high - low) * s as f64 / (options.steps - 1) as f64)
+            .collect();
+
+        let max_tiers = if show.demand.capacity > 0 {
+            options.max_tiers
+        } else {
+            1
+        };
+        for tiers in 1..=max_tiers {
+            let capacities = self.tier_capacities(show.demand.capacity, tiers);
+            let spreads: &[f64] = if tiers == 1 { &[0.0] } else { &TIER_SPREADS };
+            let mut best: Option<PricingPlan> = None;
+            for &base in &grid {
+                for &spread in spreads {
+                    let prices: Vec<f64> = (0..tiers)
+                        .map(|j| base * (1.0 + spread).powi(j as i32))
+                        .collect();
+                    // Every tier stays inside the searched range
+                    if prices[tiers - 1] > high + 1e-9 {
+                        continue;
+                    }
+                    let plan = self.evaluate(show, &prices, &capacities)?;
+                    let feasible = self.feasible(&plan);
+                    if tiers == 1 {
+                        result.curve.push(PricePoint {
+                            price: plan.tiers[0].price,
+                            tickets: plan.tickets,
+                            gross: plan.gross,
+                            revenue: plan.revenue,
+                            profit: plan.profit,
+                            feasible,
+                        });
+                    }
+                    if feasible && best.as_ref().is_none_or(|b| plan.profit > b.profit) {
+                        best = Some(plan);
+                    }
+                }
+            }
+            if let Some(plan) = best {
+                result.plans.push(plan);
+            }
+        }
+
+        // Fewer tiers win ties, being simpler to sell
+        result.optimal = result
+            .plans
+            .iter()
+            .fold(None, |best: Option<&PricingPlan>, plan| match best {
+                Some(b) if b.profit >= plan.profit => Some(b),
+                _ => Some(plan),
+            })
+            .cloned();
+        Ok(result)
+    }
+}
+
+/// Search each show's ticket price (and optionally a ladder of price tiers) for the highest
+/// profit under the demand model, subject to the options' constraints
+pub fn optimize(
+    shows: &[PricingInputs],
+    options: &PriceOptimizationOptions,
+    curve: DemandCurve,
+    currency: Currency,
+    mode: RoundingMode,
+) -> Result<PriceOptimizationReport, String> {
+    options.validate()?;
+    let optimizer = Optimizer {
+        options,
+        curve,
+        currency,
+        mode,
+    };
+    let results = shows
+        .iter()
+        .map(|show| optimizer.optimize(show))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let total_current_profit =
+        Money::sum_f64(results.iter().map(|r| r.current_profit), currency, mode).to_f64();
+    let total_optimal_profit = Money::sum_f64(
+        results.iter().map(|r| {
+            r.optimal
+                .as_ref()
+                .map_or(r.current_profit, |plan| plan.profit)
+        }),
+        currency,
+        mode,
+    )
+    .to_f64();
+
+    Ok(PriceOptimizationReport {
+        curve,
+        shows: results,
+        total_current_profit,
+        total_optimal_profit,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(value: serde_json::Value) -> PriceOptimizationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn show(deal: Option<&DealTerms>, revenue: f64) -> PricingInputs<'_> {
+        PricingInputs {
+            show_index: 0,
+            date: "2025-06-01",
+            revenue,
+            expenses: 5000.0,
+            deal,
+            promoter_expenses: 0.0,
+            demand: DemandInputs {
+                market: None,
+                capacity: 1000,
+                tickets_sold: 500,
+                ticket_price: 40.0,
+            },
+            elasticity: -1.0,
+        }
+    }
+
+    fn optimize_one(
+        show: PricingInputs,
+        options: &PriceOptimizationOptions,
+    ) -> ShowPriceOptimization {
+        optimize(
+            &[show],
+            options,
+            DemandCurve::Linear,
+            Currency::default(),
+            RoundingMode::default(),
+        )
+        .unwrap()
+        .shows
+        .remove(0)
+    }
+
+    #[test]
+    fn unit_elastic_linear_demand_peaks_at_the_current_price() {
+        let result = optimize_one(show(None, 20000.0), &options(json!({ "steps": 61 })));
+        assert_eq!(result.current_profit, 15000.0);
+        assert_eq!(result.curve.len(), 61);
+        let optimal = result.optimal.unwrap();
+        assert_eq!(optimal.tiers[0].price, 40.0);
+        assert_eq!(
+            (optimal.tickets, optimal.gross, optimal.profit),
+            (500, 20000.0, 15000.0)
+        );
+    }
+
+    #[test]
+    fn constraints_limit_the_search() {
+        let result = optimize_one(
+            show(None, 20000.0),
+            &options(json!({ "steps": 61, "price_ceiling": "30.00" })),
+        );
+        let optimal = result.optimal.unwrap();
+        assert_eq!(optimal.tiers[0].price, 30.0);
+        assert_eq!(optimal.tickets, 625);
+        assert!(result.curve.iter().all(|p| p.price <= 30.0));
+
+        let result = optimize_one(
+            show(None, 20000.0),
+            &options(json!({ "steps": 61, "min_sell_through": 60 })),
+        );
+        let optimal = result.optimal.unwrap();
+        assert!(optimal.sell_through.unwrap() >= 60.0);
+        assert!(optimal.tiers[0].price < 40.0);
+    }
+
+    #[test]
+    fn tier_ladders_stay_in_range() {
+        let result = optimize_one(
+            show(None, 20000.0),
+            &options(json!({ "steps": 61, "max_tiers": 3 })),
+        );
+        assert_eq!(result.plans.len(), 3);
+        for plan in &result.plans {
+            assert!(plan.tiers.iter().all(|t| (20.0..=80.0).contains(&t.price)));
+            let capacity: u32 = plan.tiers.iter().map(|t| t.capacity).sum();
+            assert_eq!(capacity, 1000);
+        }
+        assert!(result.optimal.unwrap().profit >= result.plans[0].profit);
+    }
+
+    #[test]
+    fn deal_shows_are_priced_on_their_payout() {
+        let door: DealTerms = serde_json::from_value(json!({
+            "type": "door", "percentage": 50, "basis": "gross_box_office"
+        }))
+        .unwrap();
+        let result = optimize_one(show(Some(&door), 10000.0), &options(json!({ "steps": 61 })));
+        assert_eq!(result.current_profit, 5000.0);
+        let optimal = result.optimal.unwrap();
+        assert_eq!(optimal.tiers[0].price, 40.0);
+        assert_eq!(
+            (optimal.gross, optimal.revenue, optimal.profit),
+            (20000.0, 10000.0, 5000.0)
+        );
+
+        // A flat fee does not move with the ticket price
+        let flat: DealTerms =
+            serde_json::from_value(json!({ "type": "flat", "guarantee": 8000 })).unwrap();
+        let result = optimize_one(show(Some(&flat), 8000.0), &options(json!({})));
+        assert!(result.curve.iter().all(|p| p.profit == 3000.0));
+        assert_eq!(result.optimal.unwrap().profit, result.current_profit);
+    }
+
+    #[test]
+    fn invalid_options_are_rejected() {
+        for value in [
+            json!({ "steps": 1 }),
+            json!({ "steps": 1_000_000 }),
+            json!({ "max_tiers": 6 }),
+            json!({ "min_price_ratio": 2, "max_price_ratio": 1 }),
+            json!({ "max_price_ratio": 1e308 }),
+            json!({ "price_ceiling": -10 }),
+            json!({ "accessible_price": "-5.00" }),
+            json!({ "min_sell_through": 120 }),
+        ] {
+            assert!(options(value.clone()).validate().is_err(), "{}", value);
+        }
+    }
+}
@@ -0,0 +1,695 @@
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::date::Date;
+use crate::monte_carlo::Distribution;
+use crate::status::{ChangeType, ReleaseStatus, ShowStatus, TaskPriority, TaskStatus};
+use crate::{TimelineChange, TimelineData, TimelineTask};
+
+pub mod leveling;
+pub mod risk;
+
+/// Working hours in one schedule day
+pub const HOURS_PER_DAY: f64 = 8.0;
+
+/// Most hours a task can be estimated at, so its duration in days stays in range
+const MAX_TASK_HOURS: f64 = 1_000_000.0;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityKind {
+    Task,
+    Release,
+    Show,
+}
+
+impl EntityKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntityKind::Task => "task",
+            EntityKind::Release => "release",
+            EntityKind::Show => "show",
+        }
+    }
+}
+
+/// A task, release or show in the dependency graph. Times are day boundaries (days since
+/// the epoch): work on day `d` runs from boundary `d` to `d + 1`.
+#[derive(Clone)]
+struct Node {
+    id: String,
+    kind: EntityKind,
+    estimated_hours: f64,
+    /// Hours of work left, shared evenly between the assignees
+    remaining_hours: f64,
+    /// Fraction of the work not yet done
+    remaining_share: f64,
+    /// Spread of the total hours, from optimistic, most likely and pessimistic estimates
+    estimate: Option<Distribution>,
+    duration: i64,
+    /// Latest allowed finish: the end of a task's deadline day, or a release or show date
+    due: i64,
+    /// Earliest allowed start, set when a change pins the entity to a new date
+    not_before: Option<i64>,
+    /// Finished work never counts towards the critical path
+    completed: bool,
+    cancelled: bool,
+    /// Scheduling rank, 0 (critical) to 3 (low)
+    priority: u8,
+    /// Resource ids working on a task
+    assignees: Vec<String>,
+    dependencies: Vec<usize>,
+}
+
+/// Dependency graph of the timeline's tasks, releases and shows, in topological order
+#[derive(Clone)]
+pub struct Graph {
+    nodes: Vec<Node>,
+    successors: Vec<Vec<usize>>,
+    order: Vec<usize>,
+}
+
+/// Earliest/latest times for one entity, as day boundaries
+#[derive(Clone, Copy, Debug)]
+struct Times {
+    es: i64,
+    ef: i64,
+    ls: i64,
+    lf: i64,
+    total_slack: i64,
+    free_slack: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScheduleEntry {
+    pub id: String,
+    pub entity_type: EntityKind,
+    pub duration_days: i64,
+    pub earliest_start: String,
+    pub earliest_finish: String,
+    pub latest_start: String,
+    pub latest_finish: String,
+    /// Days the entity can slip without missing its own or a dependent's deadline
+    pub total_slack: i64,
+    /// Days the entity can slip without delaying any dependent's earliest start
+    pub free_slack: i64,
+    pub critical: bool,
+}
+
+/// A downstream entity moved by a change
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CascadeEntry {
+    /// `type:id`, e.g. `task:mix-master`
+    pub entity: String,
+    pub id: String,
+    pub entity_type: EntityKind,
+    pub old_date: String,
+    pub new_date: String,
+    /// Days the date moves: the upstream slip minus the slack the entity had
+    pub slip_days: i64,
+    /// Dependency hops from the changed entity (1 = direct dependent)
+    pub depth: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScheduleAnalysis {
+    pub project_start: String,
+    pub project_finish: String,
+    pub entries: Vec<ScheduleEntry>,
+    /// Entities with the least total slack, in schedule order
+    pub critical_path: Vec<String>,
+}
+
+/// Share of `estimated_hours` still to do
+fn remaining_hours(estimated_hours: f64, completion_percentage: f64) -> f64 {
+    ((1.0 - completion_percentage.clamp(0.0, 100.0) / 100.0) * estimated_hours).max(0.0)
+}
+
+/// Whole days needed for `hours` of work
+fn days_for(hours: f64) -> i64 {
+    (hours / HOURS_PER_DAY).ceil() as i64
+}
+
+/// Reject hour estimates that are negative, not finite or too large to schedule
+fn check_hours(task: &TimelineTask) -> Result<(), String> {
+    let estimates = [
+        ("estimated_hours", Some(task.estimated_hours)),
+        ("optimistic_hours", task.optimistic_hours),
+        ("most_likely_hours", task.most_likely_hours),
+        ("pessimistic_hours", task.pessimistic_hours),
+    ];
+    for (field, hours) in estimates {
+        if let Some(hours) = hours.filter(|h| !(0.0..=MAX_TASK_HOURS).contains(h)) {
+            return Err(format!(
+                "Task '{}' has {} {}; hours must be between 0 and {}",
+                task.id, field, hours, MAX_TASK_HOURS
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Beta-PERT over a task's hour estimates; missing bounds fall back to the most likely
+/// hours, which default to `estimated_hours`
+fn hour_estimate(task: &TimelineTask) -> Result<Option<Distribution>, String> {
+    if task.optimistic_hours.is_none()
+        && task.most_likely_hours.is_none()
+        && task.pessimistic_hours.is_none()
+    {
+        return Ok(None);
+    }
+    let mode = task.most_likely_hours.unwrap_or(task.estimated_hours);
+    let min = task.optimistic_hours.unwrap_or(mode);
+    let max = task.pessimistic_hours.unwrap_or(mode);
+    if !(min >= 0.0 && min <= mode && mode <= max && max.is_finite()) {
+        return Err(format!(
+            "Task '{}' needs 0 <= optimistic <= most likely <= pessimistic hours",
+            task.id
+        ));
+    }
+    Ok(Some(Distribution::Pert { min, mode, max }))
+}
+
+fn day(value: &str) -> Result<i64, String> {
+    Ok(Date::parse(value)?.days_since_epoch())
+}
+
+/// Day on which work ending at boundary `finish` is done: the last day worked, or the
+/// day itself when there is no work (milestones, finished tasks)
+fn finish_day(finish: i64, duration: i64) -> i64 {
+    if duration > 0 {
+        finish - 1
+    } else {
+        finish
+    }
+}
+
+/// Boundary work of `duration` days must end by to be done on `day`
+fn finish_boundary(day: i64, duration: i64) -> i64 {
+    if duration > 0 {
+        day + 1
+    } else {
+        day
+    }
+}
+
+/// Date on which work ending at boundary `finish` is done
+fn finish_label(finish: i64, duration: i64) -> String {
+    Date::from_days_since_epoch(finish_day(finish, duration)).to_string()
+}
+
+impl Node {
+    fn finish_label(&self, finish: i64) -> String {
+        finish_label(finish, self.duration)
+    }
+}
+
+impl Graph {
+    pub fn build(data: &TimelineData) -> Result<Graph, String> {
+        let mut nodes = Vec::with_capacity(data.tasks.len() + data.releases.len());
+        for task in &data.tasks {
+            check_hours(task)?;
+            let done = task.status == TaskStatus::Completed;
+            let remaining = if done {
+                0.0
+            } else {
+                remaining_hours(task.estimated_hours, task.completion_percentage)
+            };
+            nodes.push(Node {
+                id: task.id.clone(),
+                kind: EntityKind::Task,
+                estimated_hours: task.estimated_hours,
+                remaining_hours: remaining,
+                remaining_share: if done {
+                    0.0
+                } else {
+                    1.0 - task.completion_percentage.clamp(0.0, 100.0) / 100.0
+                },
+                estimate: hour_estimate(task)?,
+                duration: days_for(remaining),
+                due: finish_boundary(day(&task.deadline)?, days_for(remaining)),
+                not_before: None,
+                completed: done,
+                cancelled: task.status == TaskStatus::Cancelled,
+                priority: task.priority.rank(),
+                assignees: task.assignees.clone(),
+                dependencies: Vec::new(),
+            });
+        }
+        for release in &data.releases {
+            nodes.push(Node {
+                id: release.id.clone(),
+                kind: EntityKind::Release,
+                estimated_hours: 0.0,
+                remaining_hours: 0.0,
+                remaining_share: 0.0,
+                estimate: None,
+                duration: 0,
+                due: day(&release.release_date)?,
+                not_before: None,
+                completed: release.status == ReleaseStatus::Released,
+                cancelled: release.status == ReleaseStatus::Cancelled,
+                priority: TaskPriority::Medium.rank(),
+                assignees: Vec::new(),
+                dependencies: Vec::new(),
+            });
+        }
+        for show in &data.shows {
+            nodes.push(Node {
+                id: show.id.clone(),
+                kind: EntityKind::Show,
+                estimated_hours: 0.0,
+                remaining_hours: 0.0,
+                remaining_share: 0.0,
+                estimate: None,
+                duration: 0,
+                due: day(&show.date)?,
+                not_before: None,
+                completed: show.status == ShowStatus::Completed,
+                cancelled: show.status == ShowStatus::Cancelled,
+                priority: TaskPriority::Medium.rank(),
+                assignees: Vec::new(),
+                dependencies: Vec::new(),
+            });
+        }
+
+        let mut index: HashMap<&str, usize> = HashMap::with_capacity(nodes.len());
+        for (i, node) in nodes.iter().enumerate() {
+            index.entry(node.id.as_str()).or_insert(i);
+        }
+        // Dependencies on unknown ids do not constrain the schedule
+        let dependency_ids = data
+            .tasks
+            .iter()
+            .map(|t| &t.dependencies)
+            .chain(data.releases.iter().map(|r| &r.dependencies))
+            .chain(data.shows.iter().map(|s| &s.dependencies));
+        let mut edges = Vec::with_capacity(nodes.len());
+        for (i, ids) in dependency_ids.enumerate() {
+            let mut deps: Vec<usize> = ids
+                .iter()
+                .filter_map(|id| index.get(id.as_str()).copied())
+                .filter(|&d| d != i)
+                .collect();
+            deps.sort_unstable();
+            deps.dedup();
+            edges.push(deps);
+        }
+        for (node, deps) in nodes.iter_mut().zip(edges) {
+            node.dependencies = deps;
+        }
+
+        let mut successors = vec![Vec::new(); nodes.len()];
+        for (i, node) in nodes.iter().enumerate() {
+            for &d in &node.dependencies {
+                successors[d].push(i);
+            }
+        }
+
+        // Kahn's algorithm, keeping input order among ready entities
+        let mut pending: Vec<usize> = nodes.iter().map(|n| n.dependencies.len()).collect();
+        let mut order: Vec<usize> = (0..nodes.len()).filter(|&i| pending[i] == 0).collect();
+        let mut next = 0;
+        while next < order.len() {
+            let current = order[next];
+            next += 1;
+            for &s in &successors[current] {
+                pending[s] -= 1;
+                if pending[s] == 0 {
+                    order.push(s);
+                }
+            }
+        }
+        if order.len() < nodes.len() {
+            let stuck: Vec<&str> = (0..nodes.len())
+                .filter(|&i| pending[i] > 0)
+                .map(|i| nodes[i].id.as_str())
+                .collect();
+            return Err(format!("Dependency cycle among: {}", stuck.join(", ")));
+        }
+
+        Ok(Graph {
+            nodes,
+            successors,
+            order,
+        })
+    }
+
+    /// Apply a proposed change: a new date pins the entity there, completing removes
+    /// its remaining work and cancelling drops it from the graph
+    pub fn apply_change(&mut self, change: &TimelineChange) -> Result<(), String> {
+        let i = match self.find(change) {
+            Some(i) => i,
+            None => return Ok(()),
+        };
+        let node = &mut self.nodes[i];
+        let due_day = finish_day(node.due, node.duration);
+
+        if let Some(completion) = change.new_completion {
+            node.remaining_hours = remaining_hours(node.estimated_hours, completion);
+            node.remaining_share = 1.0 - completion.clamp(0.0, 100.0) / 100.0;
+            node.duration = days_for(node.remaining_hours);
+        }
+        match change.change_type {
+            ChangeType::Complete => {
+                node.remaining_hours = 0.0;
+                node.remaining_share = 0.0;
+                node.duration = 0;
+                node.completed = true;
+            }
+            ChangeType::Cancel => node.cancelled = true,
+            ChangeType::Delay | ChangeType::Reschedule => {
+                if let Some(new_date) = &change.new_date {
+                    let finish = finish_boundary(day(new_date)?, node.duration);
+                    node.due = finish;
+                    node.not_before = Some(finish - node.duration);
+                    return Ok(());
+                }
+            }
+        }
+        // Keep the deadline day when the work left changes to or from none
+        node.due = finish_boundary(due_day, node.duration);
+        Ok(())
+    }
+
+    /// Node targeted by a change
+    fn find(&self, change: &TimelineChange) -> Option<usize> {
+        self.nodes
+            .iter()
+            .position(|n| n.kind == change.entity_type && n.id == change.entity_id)
+    }
+
+    /// Propagate changes to everything downstream of them. Each dependent moves by however
+    /// far its new earliest finish overshoots its planned date (its deadline, or its
+    /// projected finish if already running late), so slack absorbs upstream slips first.
+    pub fn cascade(
+        &self,
+        changes: &[TimelineChange],
+        as_of: Date,
+    ) -> Result<Vec<CascadeEntry>, String> {
+        let origins: Vec<usize> = changes.iter().filter_map(|c| self.find(c)).collect();
+        let start = as_of.days_since_epoch();
+        let before = self.passes(start);
+        let mut changed = self.clone();
+        for change in changes {
+            changed.apply_change(change)?;
+        }
+        let after = changed.passes(start);
+
+        // Breadth-first, so depth is the shortest dependency chain from any change
+        let mut depth = vec![None; self.nodes.len()];
+        for &origin in &origins {
+            depth[origin] = Some(0);
+        }
+        let mut queue: VecDeque<usize> = origins.into_iter().collect();
+        let mut reached = Vec::new();
+        while let Some(current) = queue.pop_front() {
+            let next_depth = depth[current].unwrap_or(0) + 1;
+            for &s in &self.successors[current] {
+                if depth[s].is_none() && !self.nodes[s].cancelled {
+                    depth[s] = Some(next_depth);
+                    reached.push(s);
+                    queue.push_back(s);
+                }
+            }
+        }
+
+        Ok(reached
+            .into_iter()
+            .map(|i| {
+                let node = &self.nodes[i];
+                let planned = node.due.max(before[i].ef);
+                let slip = (after[i].ef - planned).max(0);
+                CascadeEntry {
+                    entity: format!("{}:{}", node.kind.as_str(), node.id),
+                    id: node.id.clone(),
+                    entity_type: node.kind,
+                    old_date: node.finish_label(planned),
+                    new_date: node.finish_label(planned + slip),
+                    slip_days: slip,
+                    depth: depth[i].unwrap_or(0),
+                }
+            })
+            .collect())
+    }
+
+    /// Forward and backward passes from `start`
+    fn passes(&self, start: i64) -> Vec<Times> {
+        let n = self.nodes.len();
+        let mut es = vec![start; n];
+        let mut ef = vec![start; n];
+        for &i in &self.order {
+            let node = &self.nodes[i];
+            let ready = node
+                .dependencies
+                .iter()
+                .filter(|&&d| !self.nodes[d].cancelled)
+                .map(|&d| ef[d])
+                .fold(start, i64::max);
+            es[i] = ready.max(node.not_before.unwrap_or(start));
+            ef[i] = es[i] + node.duration;
+        }
+
+        let mut ls = vec![0; n];
+        let mut lf = vec![0; n];
+        for &i in self.order.iter().rev() {
+            let node = &self.nodes[i];
+            lf[i] = self.successors[i]
+                .iter()
+                .filter(|&&s| !self.nodes[s].cancelled)
+                .map(|&s| ls[s])
+                .fold(node.due, i64::min);
+            ls[i] = lf[i] - node.duration;
+        }
+
+        (0..n)
+            .map(|i| {
+                let total_slack = ls[i] - es[i];
+                let next_start = self.successors[i]
+                    .iter()
+                    .filter(|&&s| !self.nodes[s].cancelled)
+                    .map(|&s| es[s])
+                    .min();
+                let free_slack = match next_start {
+                    Some(next) => (next - ef[i]).min(total_slack),
+                    None => total_slack,
+                };
+                Times {
+                    es: es[i],
+                    ef: ef[i],
+                    ls: ls[i],
+                    lf: lf[i],
+                    total_slack,
+                    free_slack,
+                }
+            })
+            .collect()
+    }
+
+    /// Critical path method over the graph, with remaining work starting no earlier than
+    /// `as_of`. The critical path is every unfinished entity sharing the minimum total
+    /// slack, which is negative when deadlines are already unreachable.
+    pub fn analyze(&self, as_of: Date) -> ScheduleAnalysis {
+        let start = as_of.days_since_epoch();
+        let times = self.passes(start);
+        let active: Vec<usize> = self
+            .order
+            .iter()
+            .copied()
+            .filter(|&i| !self.nodes[i].cancelled)
+            .collect();
+        let min_slack = active
+            .iter()
+            .filter(|&&i| !self.nodes[i].completed)
+            .map(|&i| times[i].total_slack)
+            .min();
+        let is_critical =
+            |i: usize| !self.nodes[i].completed && Some(times[i].total_slack) == min_slack;
+
+        let mut entries = Vec::with_capacity(active.len());
+        for &i in &active {
+            let node = &self.nodes[i];
+            let t = times[i];
+            entries.push(ScheduleEntry {
+                id: node.id.clone(),
+                entity_type: node.kind,
+                duration_days: node.duration,
+                earliest_start: Date::from_days_since_epoch(t.es).to_string(),
+                earliest_finish: node.finish_label(t.ef),
+                latest_start: Date::from_days_since_epoch(t.ls).to_string(),
+                latest_finish: node.finish_label(t.lf),
+                total_slack: t.total_slack,
+                free_slack: t.free_slack,
+                critical: is_critical(i),
+            });
+        }
+
+        let mut critical: Vec<usize> = active.iter().copied().filter(|&i| is_critical(i)).collect();
+        // Stable sort keeps topological order among entities starting on the same day
+        critical.sort_by_key(|&i| times[i].es);
+
+        let project_finish = active
+            .iter()
+            .map(|&i| finish_day(times[i].ef, self.nodes[i].duration))
+            .max()
+            .map(|day| Date::from_days_since_epoch(day).to_string())
+            .unwrap_or_else(|| Date::from_days_since_epoch(start).to_string());
+
+        ScheduleAnalysis {
+            project_start: Date::from_days_since_epoch(start).to_string(),
+            project_finish,
+            entries,
+            critical_path: critical.iter().map(|&i| self.nodes[i].id.clone()).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::status::{ReleaseType, ShowStatus};
+    use crate::{TimelineRelease, TimelineShow, TimelineTask};
+
+    pub(crate) fn task(id: &str, deadline: &str, hours: f64, deps: &[&str]) -> TimelineTask {
+        TimelineTask {
+            id: id.to_string(),
+            task_type: "production".to_string(),
+            status: TaskStatus::Pending,
+            priority: TaskPriority::Medium,
+            deadline: deadline.to_string(),
+            estimated_hours: hours,
+            optimistic_hours: None,
+            most_likely_hours: None,
+            pessimistic_hours: None,
+            completion_percentage: 0.0,
+            cost_impact: 0.0,
+            revenue_impact: 0.0,
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            assignees: Vec::new(),
+        }
+    }
+
+    pub(crate) fn release(id: &str, date: &str, deps: &[&str]) -> TimelineRelease {
+        TimelineRelease {
+            id: id.to_string(),
+            release_type: ReleaseType::Single,
+            status: ReleaseStatus::Planned,
+            release_date: date.to_string(),
+            budget: 0.0,
+            projected_revenue: 0.0,
+            platforms: Vec::new(),
+            marketing_spend: 0.0,
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    pub(crate) fn show(id: &str, date: &str, deps: &[&str]) -> TimelineShow {
+        TimelineShow {
+            id: id.to_string(),
+            date: date.to_string(),
+            revenue: 0.0,
+            expenses: 0.0,
+            status: ShowStatus::Confirmed,
+            venue_capacity: 0,
+            expected_attendance: 0,
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    pub(crate) fn date(value: &str) -> Date {
+        Date::parse(value).unwrap()
+    }
+
+    fn entry<'a>(analysis: &'a ScheduleAnalysis, id: &str) -> &'a ScheduleEntry {
+        analysis.entries.iter().find(|e| e.id == id).unwrap()
+    }
+
+    #[test]
+    fn task_finishes_on_its_last_working_day() {
+        let data = TimelineData {
+            tasks: vec![task("a", "2025-01-10", 16.0, &[])],
+            releases: Vec::new(),
+            shows: Vec::new(),
+        };
+        let analysis = Graph::build(&data).unwrap().analyze(date("2025-01-06"));
+        let a = entry(&analysis, "a");
+        assert_eq!(a.earliest_start, "2025-01-06");
+        assert_eq!(a.earliest_finish, "2025-01-07");
+        assert_eq!(a.latest_finish, "2025-01-10");
+        assert_eq!(a.total_slack, 3);
+        assert_eq!(analysis.project_finish, "2025-01-07");
+    }
+
+    #[test]
+    fn zero_duration_task_finishes_on_its_start_day() {
+        let mut done = task("done", "2025-01-10", 16.0, &["a"]);
+        done.status = TaskStatus::Completed;
+        let data = TimelineData {
+            tasks: vec![
+                task("a", "2025-01-10", 16.0, &[]),
+                task("b", "2025-01-10", 0.0, &["a"]),
+                done,
+            ],
+            releases: Vec::new(),
+            shows: Vec::new(),
+        };
+        let analysis = Graph::build(&data).unwrap().analyze(date("2025-01-06"));
+        for id in ["b", "done"] {
+            let e = entry(&analysis, id);
+            assert_eq!(e.earliest_start, "2025-01-08");
+            assert_eq!(e.earliest_finish, "2025-01-08");
+            assert_eq!(e.latest_finish, "2025-01-10");
+        }
+        assert_eq!(analysis.project_finish, "2025-01-08");
+    }
+
+    #[test]
+    fn critical_path_runs_through_the_tightest_chain() {
+        let data = TimelineData {
+            tasks: vec![
+                task("mix", "2025-01-08", 16.0, &[]),
+                task("master", "2025-01-09", 8.0, &["mix"]),
+                task("artwork", "2025-01-09", 8.0, &[]),
+            ],
+            releases: vec![release("single", "2025-01-10", &["master", "artwork"])],
+            shows: Vec::new(),
+        };
+        let analysis = Graph::build(&data).unwrap().analyze(date("2025-01-06"));
+        assert_eq!(analysis.critical_path, ["mix", "master", "single"]);
+        assert_eq!(entry(&analysis, "single").earliest_finish, "2025-01-09");
+        assert_eq!(entry(&analysis, "artwork").total_slack, 3);
+    }
+
+    #[test]
+    fn cycles_are_rejected() {
+        let data = TimelineData {
+            tasks: vec![
+                task("a", "2025-01-10", 8.0, &["b"]),
+                task("b", "2025-01-10", 8.0, &["a"]),
+            ],
+            releases: Vec::new(),
+            shows: vec![show("s", "2025-02-01", &[])],
+        };
+        assert!(Graph::build(&data).is_err());
+    }
+
+    #[test]
+    fn invalid_hours_are_rejected() {
+        let build = |task: TimelineTask| {
+            Graph::build(&TimelineData {
+                tasks: vec![task],
+                releases: Vec::new(),
+                shows: Vec::new(),
+            })
+        };
+        for hours in [f64::NAN, f64::INFINITY, -8.0, 1e300] {
+            let err = build(task("mix", "2025-01-10", hours, &[])).err().unwrap();
+            assert!(err.contains("Task 'mix'"), "{}", err);
+        }
+        let mut pessimistic = task("mix", "2025-01-10", 8.0, &[]);
+        pessimistic.pessimistic_hours = Some(1e300);
+        assert!(build(pessimistic).is_err());
+        assert!(build(task("mix", "2025-01-10", 0.0, &[])).is_ok());
+    }
+}
@@ -0,0 +1,438 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::{finish_day, EntityKind, Graph};
+use crate::calendar::WorkingCalendar;
+use crate::date::Date;
+
+/// Hours below this count as no work
+const EPSILON: f64 = 1e-6;
+
+/// Days searched for free capacity before a task is reported as unschedulable
+const HORIZON_DAYS: i64 = 3660;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceKind {
+    #[default]
+    Person,
+    Crew,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Resource {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub kind: ResourceKind,
+    /// Hours available per week, spread evenly over Monday to Friday
+    pub weekly_capacity_hours: f64,
+}
+
+impl Resource {
+    fn daily_capacity(&self) -> f64 {
+        self.weekly_capacity_hours / 5.0
+    }
+}
+
+/// A week in which the unleveled schedule books a resource beyond its capacity
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct OverAllocation {
+    pub resource_id: String,
+    /// ISO week, e.g. `2025-W02`
+    pub week: String,
+    pub allocated_hours: f64,
+    pub capacity_hours: f64,
+    /// Days booked beyond the resource's daily capacity
+    pub days: Vec<String>,
+    /// Tasks booked on those days
+    pub tasks: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LeveledTask {
+    pub id: String,
+    pub assignees: Vec<String>,
+    pub start: String,
+    pub finish: String,
+    /// Finish if the task had its assignees to itself
+    pub unleveled_finish: String,
+    /// Days leveling pushes the finish back
+    pub delay_days: i64,
+    /// Days past the deadline once leveled
+    pub days_late: i64,
+}
+
+/// How far leveling moves a release or show
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MilestoneSlip {
+    /// `type:id`, e.g. `release:single-1`
+    pub entity: String,
+    pub id: String,
+    pub entity_type: EntityKind,
+    pub planned_date: String,
+    pub leveled_date: String,
+    pub slip_days: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LevelingReport {
+    /// True when the unleveled schedule books some resource beyond capacity
+    pub over_allocated: bool,
+    pub over_allocations: Vec<OverAllocation>,
+    pub tasks: Vec<LeveledTask>,
+    pub milestones: Vec<MilestoneSlip>,
+    pub unleveled_finish: String,
+    pub project_finish: String,
+}
+
+/// Start and finish boundaries per node, and the hours each (resource, day) is booked
+/// for each node
+struct Plan {
+    start: Vec<i64>,
+    finish: Vec<i64>,
+    bookings: BTreeMap<(usize, i64), Vec<(usize, f64)>>,
+}
+
+/// One resource's bookings in one week
+#[derive(Default)]
+struct WeekLoad {
+    hours: f64,
+    overbooked_days: Vec<i64>,
+    tasks: BTreeSet<usize>,
+}
+
+pub fn validate_resources(resources: &[Resource]) -> Result<(), String> {
+    let mut seen = HashSet::with_capacity(resources.len());
+    for resource in resources {
+        if !seen.insert(resource.id.as_str()) {
+            return Err(format!(
+                "Resource id '{}' is used more than once",
+                resource.id
+            ));
+        }
+        if !(resource.weekly_capacity_hours.is_finite() && resource.weekly_capacity_hours > 0.0) {
+            return Err(format!(
+                "Resource '{}' must have a positive weekly capacity",
+                resource.id
+            ));
+        }
+    }
+    Ok(())
+}
+
+impl Graph {
+    /// Level the schedule against resource capacity and compare it with the unleveled one,
+    /// where every task gets its assignees' full capacity regardless of other bookings
+    pub fn level(
+        &self,
+        resources: &[Resource],
+        calendar: &WorkingCalendar,
+        as_of: Date,
+    ) -> Result<LevelingReport, String> {
+        validate_resources(resources)?;
+        let start = as_of.days_since_epoch();
+        let unleveled = self.resource_plan(resources, calendar, start, false)?;
+        let leveled = self.resource_plan(resources, calendar, start, true)?;
+        let over_allocations = self.over_allocations(resources, calendar, &unleveled);
+
+        let active: Vec<usize> = self
+            .order
+            .iter()
+            .copied()
+            .filter(|&i| !self.nodes[i].cancelled)
+            .collect();
+        let mut tasks = Vec::new();
+        let mut milestones = Vec::new();
+        for &i in &active {
+            let node = &self.nodes[i];
+            match node.kind {
+                EntityKind::Task if node.completed => {}
+                EntityKind::Task => tasks.push(LeveledTask {
+                    id: node.id.clone(),
+                    assignees: node.assignees.clone(),
+                    start: Date::from_days_since_epoch(leveled.start[i]).to_string(),
+                    finish: node.finish_label(leveled.finish[i]),
+                    unleveled_finish: node.finish_label(unleveled.finish[i]),
+                    delay_days: leveled.finish[i] - unleveled.finish[i],
+                    days_late: (leveled.finish[i] - node.due).max(0),
+                }),
+                EntityKind::Release | EntityKind::Show => {
+                    let planned = node.due.max(unleveled.finish[i]);
+                    let moved = node.due.max(leveled.finish[i]);
+                    milestones.push(MilestoneSlip {
+                        entity: format!("{}:{}", node.kind.as_str(), node.id),
+                        id: node.id.clone(),
+                        entity_type: node.kind,
+                        planned_date: node.finish_label(planned),
+                        leveled_date: node.finish_label(moved),
+                        slip_days: moved - planned,
+                    });
+                }
+            }
+        }
+
+        let finish_label = |plan: &Plan| {
+            active
+                .iter()
+                .map(|&i| finish_day(plan.finish[i], self.nodes[i].duration))
+                .max()
+                .map(|day| Date::from_days_since_epoch(day).to_string())
+                .unwrap_or_else(|| Date::from_days_since_epoch(start).to_string())
+        };
+
+        Ok(LevelingReport {
+            over_allocated: !over_allocations.is_empty(),
+            over_allocations,
+            tasks,
+            milestones,
+            unleveled_finish: finish_label(&unleveled),
+            project_finish: finish_label(&leveled),
+        })
+    }
+
+    /// Serial schedule generation: repeatedly take the ready entity with the best priority,
+    /// then the least slack, and book its work on the earliest working days its assignees
+    /// have capacity for. With `shared` off every task sees its assignees as free.
+    fn resource_plan(
+        &self,
+        resources: &[Resource],
+        calendar: &WorkingCalendar,
+        start: i64,
+        shared: bool,
+    ) -> Result<Plan, String> {
+        let index: HashMap<&str, usize> = resources
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (r.id.as_str(), i))
+            .collect();
+        let mut assigned = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let mut ids = Vec::with_capacity(node.assignees.len());
+            for assignee in &node.assignees {
+                match index.get(assignee.as_str()) {
+                    Some(&r) if !ids.contains(&r) => ids.push(r),
+                    Some(_) => {}
+                    None => {
+                        return Err(format!(
+                            "Task '{}' is assigned to unknown resource '{}'",
+                            node.id, assignee
+                        ))
+                    }
+                }
+            }
+            assigned.push(ids);
+        }
+
+        let times = self.passes(start);
+        let n = self.nodes.len();
+        let mut pending: Vec<usize> = self.nodes.iter().map(|n| n.dependencies.len()).collect();
+        let mut ready: Vec<usize> = (0..n).filter(|&i| pending[i] == 0).collect();
+        let mut plan = Plan {
+            start: vec![start; n],
+            finish: vec![start; n],
+            bookings: BTreeMap::new(),
+        };
+        let mut used: HashMap<(usize, i64), f64> = HashMap::new();
+
+        while let Some(pos) = (0..ready.len())
+            .min_by_key(|&p| (self.nodes[ready[p]].priority, times[ready[p]].ls, ready[p]))
+        {
+            let i = ready.swap_remove(pos);
+            let node = &self.nodes[i];
+            let ready_at = node
+                .dependencies
+                .iter()
+                .filter(|&&d| !self.nodes[d].cancelled)
+                .map(|&d| plan.finish[d])
+                .fold(start, i64::max)
+                .max(node.not_before.unwrap_or(start));
+            plan.start[i] = ready_at;
+
+            if node.cancelled {
+                plan.finish[i] = ready_at;
+            } else if assigned[i].is_empty() || node.remaining_hours <= EPSILON {
+                plan.finish[i] = ready_at + node.duration;
+            } else {
+                let share = node.remaining_hours / assigned[i].len() as f64;
+                let mut left = vec![share; assigned[i].len()];
+                let mut first = None;
+                let mut last = ready_at;
+                let mut day = ready_at;
+                while left.iter().any(|&h| h > EPSILON) {
+                    if day - ready_at > HORIZON_DAYS {
+                        return Err(format!(
+                            "Task '{}' cannot be scheduled within {} days of {}",
+                            node.id,
+                            HORIZON_DAYS,
+                            Date::from_days_since_epoch(ready_at)
+                        ));
+                    }
+                    if calendar.is_working_day(&Date::from_days_since_epoch(day)) {
+                        for (k, &r) in assigned[i].iter().enumerate() {
+                            let booked = if shared {
+                                used.get(&(r, day)).copied().unwrap_or(0.0)
+                            } else {
+                                0.0
+                            };
+                            let hours = left[k].min(resources[r].daily_capacity() - booked);
+                            if hours > EPSILON {
+                                left[k] -= hours;
+                                *used.entry((r, day)).or_insert(0.0) += hours;
+                                plan.bookings.entry((r, day)).or_default().push((i, hours));
+                                first.get_or_insert(day);
+                                last = day;
+                            }
+                        }
+                    }
+                    day += 1;
+                }
+                plan.start[i] = first.unwrap_or(ready_at);
+                plan.finish[i] = last + 1;
+            }
+
+            for &s in &self.successors[i] {
+                pending[s] -= 1;
+                if pending[s] == 0 {
+                    ready.push(s);
+                }
+            }
+        }
+        Ok(plan)
+    }
+
+    /// Weeks containing days where a plan books a resource beyond its daily capacity
+    fn over_allocations(
+        &self,
+        resources: &[Resource],
+        calendar: &WorkingCalendar,
+        plan: &Plan,
+    ) -> Vec<OverAllocation> {
+        let mut weeks: BTreeMap<(usize, i64), WeekLoad> = BTreeMap::new();
+        for (&(r, day), booked) in &plan.bookings {
+            let week = Date::from_days_since_epoch(day).week_index();
+            let hours: f64 = booked.iter().map(|&(_, h)| h).sum();
+            let load = weeks.entry((r, week)).or_default();
+            load.hours += hours;
+            if hours > resources[r].daily_capacity() + EPSILON {
+                load.overbooked_days.push(day);
+                load.tasks.extend(booked.iter().map(|&(i, _)| i));
+            }
+        }
+
+        weeks
+            .into_iter()
+            .filter(|(_, load)| !load.overbooked_days.is_empty())
+            .map(|((r, week), load)| {
+                let monday = Date::from_week_index(week);
+                let working_days = (0..5)
+                    .filter(|&d| calendar.is_working_day(&monday.add_days(d)))
+                    .count();
+                OverAllocation {
+                    resource_id: resources[r].id.clone(),
+                    week: monday.iso_week_label(),
+                    allocated_hours: load.hours,
+                    capacity_hours: resources[r].daily_capacity() * working_days as f64,
+                    days: load
+                        .overbooked_days
+                        .iter()
+                        .map(|&d| Date::from_days_since_epoch(d).to_string())
+                        .collect(),
+                    tasks: load
+                        .tasks
+                        .iter()
+                        .map(|&i| self.nodes[i].id.clone())
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::tests::{date, release, task};
+    use crate::TimelineData;
+
+    fn resource(id: &str, weekly_capacity_hours: f64) -> Resource {
+        Resource {
+            id: id.to_string(),
+            name: None,
+            kind: ResourceKind::Person,
+            weekly_capacity_hours,
+        }
+    }
+
+    fn assigned(id: &str, deadline: &str, hours: f64, assignee: &str) -> crate::TimelineTask {
+        let mut task = task(id, deadline, hours, &[]);
+        task.assignees = vec![assignee.to_string()];
+        task
+    }
+
+    fn level(data: &TimelineData, resources: &[Resource]) -> Result<LevelingReport, String> {
+        Graph::build(data)?.level(resources, &WorkingCalendar::default(), date("2025-01-06"))
+    }
+
+    #[test]
+    fn shared_resource_pushes_the_later_task_back() {
+        let data = TimelineData {
+            tasks: vec![
+                assigned("mix", "2025-01-10", 16.0, "alice"),
+                assigned("master", "2025-01-31", 16.0, "alice"),
+            ],
+            releases: vec![release("single", "2025-01-08", &["master"])],
+            shows: Vec::new(),
+        };
+        let report = level(&data, &[resource("alice", 40.0)]).unwrap();
+
+        // The release due on the 8th makes the master the more urgent task
+        let master = report.tasks.iter().find(|t| t.id == "master").unwrap();
+        assert_eq!(master.delay_days, 0);
+        assert_eq!(report.milestones[0].slip_days, 0);
+        let mix = report.tasks.iter().find(|t| t.id == "mix").unwrap();
+        assert_eq!(mix.start, "2025-01-08");
+        assert_eq!(mix.delay_days, 2);
+
+        assert!(report.over_allocated);
+        let week = &report.over_allocations[0];
+        assert_eq!(week.resource_id, "alice");
+        assert_eq!((week.allocated_hours, week.capacity_hours), (32.0, 40.0));
+        assert_eq!(week.days, vec!["2025-01-06", "2025-01-07"]);
+        assert_eq!(week.tasks, vec!["mix", "master"]);
+        assert!(report.project_finish > report.unleveled_finish);
+    }
+
+    #[test]
+    fn separate_resources_do_not_interfere() {
+        let data = TimelineData {
+            tasks: vec![
+                assigned("mix", "2025-01-10", 16.0, "alice"),
+                assigned("master", "2025-01-10", 16.0, "bob"),
+            ],
+            releases: Vec::new(),
+            shows: Vec::new(),
+        };
+        let report = level(&data, &[resource("alice", 40.0), resource("bob", 40.0)]).unwrap();
+        assert!(!report.over_allocated);
+        assert!(report.tasks.iter().all(|t| t.delay_days == 0));
+        assert_eq!(report.project_finish, report.unleveled_finish);
+    }
+
+    #[test]
+    fn invalid_resources_are_rejected() {
+        assert!(validate_resources(&[resource("alice", 40.0), resource("alice", 20.0)]).is_err());
+        assert!(validate_resources(&[resource("alice", 0.0)]).is_err());
+        assert!(validate_resources(&[resource("alice", f64::NAN)]).is_err());
+
+        let data = TimelineData {
+            tasks: vec![assigned("mix", "2025-01-10", 8.0, "carol")],
+            releases: Vec::new(),
+            shows: Vec::new(),
+        };
+        let err = level(&data, &[resource("alice", 40.0)]).unwrap_err();
+        assert!(err.contains("unknown resource 'carol'"));
+    }
+}
@@ -0,0 +1,333 @@
+use serde::{Deserialize, Serialize};
+
+use super::{days_for, finish_day, EntityKind, Graph};
+use crate::date::Date;
+use crate::monte_carlo::{percentile, Rng, DEFAULT_SEED};
+
+/// Most iterations one run keeps; every milestone and task date is stored per iteration
+const MAX_ITERATIONS: u32 = 10_000;
+
+/// Most tasks listed in `contributors`
+const MAX_CONTRIBUTORS: usize = 100;
+
+fn default_top_contributors() -> usize {
+    5
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ScheduleRiskOptions {
+    pub iterations: u32,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Number of tasks to list in `contributors`
+    #[serde(default = "default_top_contributors")]
+    pub top_contributors: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MilestoneRisk {
+    /// `type:id`, e.g. `show:berlin`
+    pub entity: String,
+    pub id: String,
+    pub entity_type: EntityKind,
+    pub target_date: String,
+    /// Share of iterations (0-1) in which everything the milestone depends on is done in time
+    pub on_time_probability: f64,
+    pub p50_date: String,
+    pub p90_date: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RiskContributor {
+    pub id: String,
+    pub mean_days: f64,
+    pub std_dev_days: f64,
+    /// Share of iterations (0-1) in which the task is on the critical path
+    pub criticality_index: f64,
+    /// Schedule sensitivity index: criticality times the task's spread relative to the
+    /// project's. Higher means more of the finish date's uncertainty comes from this task.
+    pub sensitivity: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScheduleRiskResult {
+    pub iterations: u32,
+    pub seed: u64,
+    pub milestones: Vec<MilestoneRisk>,
+    pub project_p50: String,
+    pub project_p90: String,
+    /// Tasks contributing most to schedule risk, riskiest first
+    pub contributors: Vec<RiskContributor>,
+}
+
+fn mean_std_dev(values: &[f64]) -> (f64, f64) {
+    if values.is_empty() {
+        return (0.0, 0.0);
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance.sqrt())
+}
+
+/// Date at percentile `p` of a sample of day numbers, rounded up to a whole day
+fn date_at(sorted: &[f64], p: f64) -> String {
+    Date::from_days_since_epoch(percentile(sorted, p).ceil() as i64).to_string()
+}
+
+impl Graph {
+    /// Monte Carlo over task durations: each iteration samples every unfinished task's hours
+    /// from its three-point estimate (tasks without one keep their fixed estimate) and runs
+    /// the forward and backward passes from `as_of`
+    pub fn simulate_risk(
+        &self,
+        options: &ScheduleRiskOptions,
+        as_of: Date,
+    ) -> Result<ScheduleRiskResult, String> {
+        if !(1..=MAX_ITERATIONS).contains(&options.iterations) {
+            return Err(format!(
+                "iterations must be between 1 and {}",
+                MAX_ITERATIONS
+            ));
+        }
+        if options.top_contributors > MAX_CONTRIBUTORS {
+            return Err(format!(
+                "top_contributors must be at most {}",
+                MAX_CONTRIBUTORS
+            ));
+        }
+        for node in &self.nodes {
+            if let Some(estimate) = &node.estimate {
+                estimate.validate()?;
+            }
+        }
+
+        let start = as_of.days_since_epoch();
+        let seed = options.seed.unwrap_or(DEFAULT_SEED);
+        let mut rng = Rng::new(seed);
+        let iterations = options.iterations as usize;
+
+        let active: Vec<usize> = self
+            .order
+            .iter()
+            .copied()
+            .filter(|&i| !self.nodes[i].cancelled)
+            .collect();
+        let milestones: Vec<usize> = active
+            .iter()
+            .copied()
+            .filter(|&i| self.nodes[i].kind != EntityKind::Task)
+            .collect();
+        let tasks: Vec<usize> = active
+            .iter()
+            .copied()
+            .filter(|&i| self.nodes[i].kind == EntityKind::Task && !self.nodes[i].completed)
+            .collect();
+
+        let mut milestone_days = vec![Vec::with_capacity(iterations); milestones.len()];
+        let mut on_time = vec![0u32; milestones.len()];
+        let mut project_days = Vec::with_capacity(iterations);
+        let mut task_days = vec![Vec::with_capacity(iterations); tasks.len()];
+        let mut critical = vec![0u32; tasks.len()];
+
+        let n = self.nodes.len();
+        let mut duration = vec![0i64; n];
+        let mut es = vec![start; n];
+        let mut ef = vec![start; n];
+        let mut ls = vec![start; n];
+        for _ in 0..iterations {
+            for &i in &tasks {
+                let node = &self.nodes[i];
+                duration[i] = match &node.estimate {
+                    Some(estimate) => {
+                        days_for(estimate.sample(&mut rng).max(0.0) * node.remaining_share)
+                    }
+                    None => node.duration,
+                };
+            }
+
+            for &i in &self.order {
+                let node = &self.nodes[i];
+                let ready = node
+                    .dependencies
+                    .iter()
+                    .filter(|&&d| !self.nodes[d].cancelled)
+                    .map(|&d| ef[d])
+                    .fold(start, i64::max);
+                es[i] = ready.max(node.not_before.unwrap_or(start));
+                ef[i] = es[i] + duration[i];
+            }
+            let project_end = active.iter().map(|&i| ef[i]).fold(start, i64::max);
+            for &i in self.order.iter().rev() {
+                let lf = self.successors[i]
+                    .iter()
+                    .filter(|&&s| !self.nodes[s].cancelled)
+                    .map(|&s| ls[s])
+                    .fold(project_end, i64::min);
+                ls[i] = lf - duration[i];
+            }
+
+            // Finish dates as labelled; releases and shows still happen on their own date
+            // when everything before them is done early
+            let label_day = |i: usize| {
+                let node = &self.nodes[i];
+                match node.kind {
+                    EntityKind::Task => finish_day(ef[i], duration[i]),
+                    EntityKind::Release | EntityKind::Show => ef[i].max(node.due),
+                }
+            };
+            project_days.push(active.iter().map(|&i| label_day(i)).max().unwrap_or(start) as f64);
+            for (k, &i) in milestones.iter().enumerate() {
+                milestone_days[k].push(label_day(i) as f64);
+                if ef[i] <= self.nodes[i].due {
+                    on_time[k] += 1;
+                }
+            }
+            for (k, &i) in tasks.iter().enumerate() {
+                task_days[k].push(duration[i] as f64);
+                if ls[i] == es[i] {
+                    critical[k] += 1;
+                }
+            }
+        }
+
+        let (_, project_std_dev) = mean_std_dev(&project_days);
+        let mut contributors: Vec<RiskContributor> = tasks
+            .iter()
+            .enumerate()
+            .map(|(k, &i)| {
+                let (mean_days, std_dev_days) = mean_std_dev(&task_days[k]);
+                let criticality_index = critical[k] as f64 / iterations as f64;
+                let sensitivity = if project_std_dev > 0.0 {
+                    criticality_index * std_dev_days / project_std_dev
+                } else {
+                    0.0
+                };
+                RiskContributor {
+                    id: self.nodes[i].id.clone(),
+                    mean_days,
+                    std_dev_days,
+                    criticality_index,
+                    sensitivity,
+                }
+            })
+            .collect();
+        // Stable sort keeps schedule order among equally risky tasks
+        contributors.sort_by(|a, b| {
+            b.sensitivity
+                .total_cmp(&a.sensitivity)
+                .then(b.criticality_index.total_cmp(&a.criticality_index))
+        });
+        contributors.truncate(options.top_contributors);
+
+        let milestones = milestones
+            .iter()
+            .enumerate()
+            .map(|(k, &i)| {
+                let node = &self.nodes[i];
+                let days = &mut milestone_days[k];
+                days.sort_by(|a, b| a.total_cmp(b));
+                MilestoneRisk {
+                    entity: format!("{}:{}", node.kind.as_str(), node.id),
+                    id: node.id.clone(),
+                    entity_type: node.kind,
+                    target_date: node.finish_label(node.due),
+                    on_time_probability: on_time[k] as f64 / iterations as f64,
+                    p50_date: date_at(days, 50.0),
+                    p90_date: date_at(days, 90.0),
+                }
+            })
+            .collect();
+
+        project_days.sort_by(|a, b| a.total_cmp(b));
+        Ok(ScheduleRiskResult {
+            iterations: options.iterations,
+            seed,
+            milestones,
+            project_p50: date_at(&project_days, 50.0),
+            project_p90: date_at(&project_days, 90.0),
+            contributors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::tests::{date, release, task};
+    use crate::TimelineData;
+
+    fn options(iterations: u32) -> ScheduleRiskOptions {
+        ScheduleRiskOptions {
+            iterations,
+            seed: Some(7),
+            top_contributors: 5,
+        }
+    }
+
+    #[test]
+    fn early_work_keeps_the_release_on_its_date() {
+        let data = TimelineData {
+            tasks: vec![task("master", "2025-01-08", 8.0, &[])],
+            releases: vec![release("single", "2025-01-31", &["master"])],
+            shows: Vec::new(),
+        };
+        let graph = Graph::build(&data).unwrap();
+        let result = graph
+            .simulate_risk(&options(50), date("2025-01-06"))
+            .unwrap();
+        let single = &result.milestones[0];
+        assert_eq!(single.on_time_probability, 1.0);
+        assert_eq!(single.p50_date, "2025-01-31");
+        assert_eq!(single.p90_date, "2025-01-31");
+        assert_eq!(result.project_p90, "2025-01-31");
+    }
+
+    #[test]
+    fn uncertain_work_pushes_the_release_late() {
+        let mut master = task("master", "2025-01-08", 8.0, &[]);
+        master.optimistic_hours = Some(8.0);
+        master.most_likely_hours = Some(40.0);
+        master.pessimistic_hours = Some(160.0);
+        let data = TimelineData {
+            tasks: vec![master],
+            releases: vec![release("single", "2025-01-10", &["master"])],
+            shows: Vec::new(),
+        };
+        let graph = Graph::build(&data).unwrap();
+        let first = graph
+            .simulate_risk(&options(500), date("2025-01-06"))
+            .unwrap();
+        let single = &first.milestones[0];
+        assert!(single.on_time_probability < 0.5);
+        assert!(single.p90_date > single.p50_date);
+        assert!(single.p50_date > single.target_date);
+        assert_eq!(first.contributors[0].id, "master");
+        assert_eq!(first.contributors[0].criticality_index, 1.0);
+
+        // Seeded runs are reproducible
+        let again = graph
+            .simulate_risk(&options(500), date("2025-01-06"))
+            .unwrap();
+        assert_eq!(again.milestones[0].p90_date, single.p90_date);
+    }
+
+    #[test]
+    fn out_of_range_options_are_rejected() {
+        let data = TimelineData {
+            tasks: Vec::new(),
+            releases: Vec::new(),
+            shows: Vec::new(),
+        };
+        let graph = Graph::build(&data).unwrap();
+        let as_of = date("2025-01-06");
+        assert!(graph.simulate_risk(&options(0), as_of).is_err());
+        assert!(graph.simulate_risk(&options(u32::MAX), as_of).is_err());
+        let contributors = ScheduleRiskOptions {
+            top_contributors: usize::MAX,
+            ..options(10)
+        };
+        assert!(graph.simulate_risk(&contributors, as_of).is_err());
+        assert!(graph.simulate_risk(&options(MAX_ITERATIONS), as_of).is_ok());
+    }
+}
@@ -0,0 +1,232 @@
+use serde::{Deserialize, Serialize};
+
+use crate::money::{Currency, Money, RoundingMode};
+
+/// Assumption moved by a sensitivity run
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Driver {
+    /// Ticket price, with ticket sales following the demand model
+    TicketPrice,
+    /// Tickets sold at today's prices, up to capacity
+    Attendance,
+    /// Every show's expenses
+    Expenses,
+    /// Value of foreign currencies against the base currency
+    FxRate,
+    /// Withholding tax rates
+    Wht,
+    /// Commission rates
+    Commission,
+    /// The travel part of each show's expenses
+    TravelCost,
+}
+
+pub const ALL_DRIVERS: [Driver; 7] = [
+    Driver::TicketPrice,
+    Driver::Attendance,
+    Driver::Expenses,
+    Driver::FxRate,
+    Driver::Wht,
+    Driver::Commission,
+    Driver::TravelCost,
+];
+
+/// Most points per spider series
+const MAX_STEPS: usize = 101;
+
+fn default_range() -> f64 {
+    20.0
+}
+
+fn default_steps() -> usize {
+    9
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SensitivityOptions {
+    /// Each driver moves from -range% to +range% (0-100)
+    #[serde(default = "default_range")]
+    pub range: f64,
+    /// Points per spider series, evenly spaced across the range
+    #[serde(default = "default_steps")]
+    pub steps: usize,
+    /// Drivers to vary; all of them when empty
+    #[serde(default)]
+    pub drivers: Vec<Driver>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TornadoBar {
+    pub driver: Driver,
+    /// Net profit with the driver at -range%
+    pub low_profit: f64,
+    /// Net profit with the driver at +range%
+    pub high_profit: f64,
+    /// Distance between the lowest and highest profit across the range
+    pub swing: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SpiderPoint {
+    /// Percentage change applied to the driver
+    pub change: f64,
+    pub net_profit: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SpiderSeries {
+    pub driver: Driver,
+    pub points: Vec<SpiderPoint>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SensitivityResult {
+    pub base_net_profit: f64,
+    pub range: f64,
+    /// Largest swing first
+    pub tornado: Vec<TornadoBar>,
+    /// In the order the drivers were given
+    pub spider: Vec<SpiderSeries>,
+}
+
+impl SensitivityOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.range > 0.0 && self.range <= 100.0) {
+            return Err("range must be greater than 0 and at most 100".to_string());
+        }
+        if !(3..=MAX_STEPS).contains(&self.steps) {
+            return Err(format!("steps must be between 3 and {}", MAX_STEPS));
+        }
+        Ok(())
+    }
+}
+
+/// Vary each driver on its own across the range, holding the others at their current
+/// values. `net_profit` gives the tour's net profit with one driver moved by a percentage.
+pub fn analyze(
+    options: &SensitivityOptions,
+    currency: Currency,
+    mode: RoundingMode,
+    net_profit: impl Fn(Driver, f64) -> Result<f64, String>,
+) -> Result<SensitivityResult, String> {
+    options.validate()?;
+    let drivers: &[Driver] = if options.drivers.is_empty() {
+        &ALL_DRIVERS
+    } else {
+        &options.drivers
+    };
+    let base_net_profit = net_profit(ALL_DRIVERS[0], 0.0)?;
+    let changes: Vec<f64> = (0..options.steps)
+        .map(|i| -options.range + 2.0 * options.range * i as f64 / (options.steps - 1) as f64)
+        .collect();
+
+    let mut tornado = Vec::with_capacity(drivers.len());
+    let mut spider = Vec::with_capacity(drivers.len());
+    for &driver in drivers {
+        let points = changes
+            .iter()
+            .map(|&change| {
+                Ok(SpiderPoint {
+                    change,
+                    net_profit: net_profit(driver, change)?,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let profits = points.iter().map(|p| p.net_profit);
+        let lowest = profits.clone().fold(f64::INFINITY, f64::min);
+        let highest = profits.fold(f64::NEG_INFINITY, f64::max);
+        tornado.push(TornadoBar {
+            driver,
+            low_profit: points[0].net_profit,
+            high_profit: points[points.len() - 1].net_profit,
+            swing: Money::from_f64(highest - lowest, currency, mode).to_f64(),
+        });
+        spider.push(SpiderSeries { driver, points });
+    }
+    tornado.sort_by(|a, b| b.swing.total_cmp(&a.swing));
+
+    Ok(SensitivityResult {
+        base_net_profit,
+        range: options.range,
+        tornado,
+        spider,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(value: serde_json::Value) -> SensitivityOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    /// Revenue of 10000 against 6000 of expenses, a third of it travel
+    fn profit(driver: Driver, change: f64) -> Result<f64, String> {
+        let factor = 1.0 + change / 100.0;
+        Ok(match driver {
+            Driver::TicketPrice => 10000.0 * factor - 6000.0,
+            Driver::Expenses => 10000.0 - 6000.0 * factor,
+            Driver::TravelCost => 10000.0 - 4000.0 - 2000.0 * factor,
+            _ => 4000.0,
+        })
+    }
+
+    fn run(value: serde_json::Value) -> SensitivityResult {
+        analyze(
+            &options(value),
+            Currency::default(),
+            RoundingMode::default(),
+            profit,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn tornado_is_sorted_by_swing() {
+        let result = run(json!({}));
+        assert_eq!(result.base_net_profit, 4000.0);
+        let order: Vec<Driver> = result.tornado.iter().take(3).map(|b| b.driver).collect();
+        assert_eq!(
+            order,
+            [Driver::TicketPrice, Driver::Expenses, Driver::TravelCost]
+        );
+        let price = &result.tornado[0];
+        assert_eq!(
+            (price.low_profit, price.high_profit, price.swing),
+            (2000.0, 6000.0, 4000.0)
+        );
+        let expenses = &result.tornado[1];
+        assert_eq!(
+            (expenses.low_profit, expenses.high_profit),
+            (5200.0, 2800.0)
+        );
+        assert_eq!(result.tornado[6].swing, 0.0);
+    }
+
+    #[test]
+    fn spider_points_span_the_range() {
+        let result = run(json!({ "range": 10, "steps": 5, "drivers": ["expenses"] }));
+        assert_eq!(result.spider.len(), 1);
+        let changes: Vec<f64> = result.spider[0].points.iter().map(|p| p.change).collect();
+        assert_eq!(changes, [-10.0, -5.0, 0.0, 5.0, 10.0]);
+        assert_eq!(
+            result.spider[0].points[2].net_profit,
+            result.base_net_profit
+        );
+    }
+
+    #[test]
+    fn options_are_bounded() {
+        for value in [
+            json!({ "range": 0 }),
+            json!({ "range": 150 }),
+            json!({ "steps": 2 }),
+            json!({ "steps": 1_000_000 }),
+        ] {
+            assert!(options(value.clone()).validate().is_err(), "{}", value);
+        }
+    }
+}
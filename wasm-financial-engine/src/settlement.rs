@@ -0,0 +1,361 @@
+use serde::{Deserialize, Serialize};
+
+use crate::deal::{DealInputs, DealPayout, DealStructure, DealTerms};
+use crate::money::{self, Currency, Money, RoundingMode};
+
+/// Tickets sold at one price point
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TierSales {
+    pub name: String,
+    #[serde(deserialize_with = "money::deserialize_amount")]
+    pub price: f64,
+    pub sold: u32,
+    #[serde(default)]
+    pub comps: u32,
+}
+
+/// How a box office deduction is calculated
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DeductionRule {
+    Flat {
+        #[serde(deserialize_with = "money::deserialize_amount")]
+        amount: f64,
+    },
+    /// Charged on every paid ticket
+    PerTicket {
+        #[serde(deserialize_with = "money::deserialize_amount")]
+        amount: f64,
+    },
+    /// Percentage (0-100) of gross
+    PercentOfGross { percentage: f64 },
+    /// Tax already included in the ticket price, e.g. 21% VAT extracts 21/121 of gross
+    IncludedTax { percentage: f64 },
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Deduction {
+    pub name: String,
+    #[serde(flatten)]
+    pub rule: DeductionRule,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PromoterCost {
+    pub name: String,
+    #[serde(deserialize_with = "money::deserialize_amount")]
+    pub amount: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SettlementInput {
+    /// Loaded show to take the currency, capacity and deal terms from
+    #[serde(default)]
+    pub show_index: Option<usize>,
+    #[serde(default)]
+    pub currency: Option<Currency>,
+    #[serde(default)]
+    pub capacity: Option<u32>,
+    pub tiers: Vec<TierSales>,
+    #[serde(default)]
+    pub ticketing_fees: Vec<Deduction>,
+    #[serde(default)]
+    pub facility_fees: Vec<Deduction>,
+    #[serde(default)]
+    pub taxes: Vec<Deduction>,
+    #[serde(default)]
+    pub promoter_costs: Vec<PromoterCost>,
+    #[serde(default)]
+    pub deal: Option<DealTerms>,
+    #[serde(default, deserialize_with = "money::deserialize_amount")]
+    pub deposits_paid: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TierLine {
+    pub name: String,
+    pub price: f64,
+    pub sold: u32,
+    pub comps: u32,
+    pub gross: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LineItem {
+    pub category: String,
+    pub name: String,
+    pub amount: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SettlementSheet {
+    pub currency: Currency,
+    pub tiers: Vec<TierLine>,
+    pub tickets_sold: u32,
+    pub comps: u32,
+    pub gross_box_office: f64,
+    pub deductions: Vec<LineItem>,
+    pub total_deductions: f64,
+    /// Net box office receipts: gross minus ticketing fees, facility fees and taxes
+    pub net_box_office_receipts: f64,
+    pub promoter_costs: Vec<LineItem>,
+    pub total_promoter_costs: f64,
+    pub net_after_costs: f64,
+    pub deal: DealPayout,
+    pub guarantee: f64,
+    pub artist_share: f64,
+    /// Artist share above the guarantee
+    pub overage: f64,
+    pub deposits_paid: f64,
+    pub amount_due: f64,
+}
+
+fn deduction_amount(
+    rule: &DeductionRule,
+    gross: Money,
+    paid_tickets: u32,
+    mode: RoundingMode,
+) -> Result<Money, String> {
+    let currency = gross.currency();
+    let check = |p: f64| {
+        if (0.0..=100.0).contains(&p) {
+            Ok(())
+        } else {
+            Err(format!(
+                "Deduction percentage {} must be between 0 and 100",
+                p
+            ))
+        }
+    };
+    Ok(match *rule {
+        DeductionRule::Flat { amount } => Money::from_f64(amount, currency, mode),
+        DeductionRule::PerTicket { amount } => {
+            Money::from_f64(amount, currency, mode).times(paid_tickets as i64)?
+        }
+        DeductionRule::PercentOfGross { percentage } => {
+            check(percentage)?;
+            gross.multiply(percentage / 100.0, mode)?
+        }
+        DeductionRule::IncludedTax { percentage } => {
+            check(percentage)?;
+            gross.multiply(percentage / (100.0 + percentage), mode)?
+        }
+    })
+}
+
+/// Build an itemized settlement sheet. Gross deals are settled against the gross box
+/// office; net deals against net box office receipts less the promoter costs.
+pub fn settle(
+    input: &SettlementInput,
+    deal: &DealTerms,
+    currency: Currency,
+    capacity: u32,
+    mode: RoundingMode,
+) -> Result<SettlementSheet, String> {
+    if input.tiers.is_empty() {
+        return Err("Settlement needs at least one ticket tier".to_string());
+    }
+    let money = |amount: f64| Money::from_f64(amount, currency, mode);
+
+    let mut gross = Money::zero(currency);
+    let mut tiers = Vec::with_capacity(input.tiers.len());
+    for tier in &input.tiers {
+        let tier_gross = money(tier.price).times(tier.sold as i64)?;
+        gross = gross.checked_add(&tier_gross)?;
+        tiers.push(TierLine {
+            name: tier.name.clone(),
+            price: tier.price,
+            sold: tier.sold,
+            comps: tier.comps,
+            gross: tier_gross.to_f64(),
+        });
+    }
+    let count = |tickets: fn(&TierSales) -> u32| {
+        input
+            .tiers
+            .iter()
+            .try_fold(0u32, |total, tier| total.checked_add(tickets(tier)))
+            .ok_or_else(|| "Ticket count is too large".to_string())
+    };
+    let tickets_sold = count(|t| t.sold)?;
+    let comps = count(|t| t.comps)?;
+
+    let mut deductions = Vec::new();
+    let mut total_deductions = Money::zero(currency);
+    let categories = [
+        ("ticketing_fee", &input.ticketing_fees),
+        ("facility_fee", &input.facility_fees),
+        ("tax", &input.taxes),
+    ];
+    for (category, items) in categories {
+        for item in items {
+            let amount = deduction_amount(&item.rule, gross, tickets_sold, mode)?;
+            total_deductions = total_deductions.checked_add(&amount)?;
+            deductions.push(LineItem {
+                category: category.to_string(),
+                name: item.name.clone(),
+                amount: amount.to_f64(),
+            });
+        }
+    }
+    let nbor = gross.checked_sub(&total_deductions)?;
+
+    let mut promoter_costs = Vec::with_capacity(input.promoter_costs.len());
+    let mut total_costs = Money::zero(currency);
+    for cost in &input.promoter_costs {
+        let amount = money(cost.amount);
+        total_costs = total_costs.checked_add(&amount)?;
+        promoter_costs.push(LineItem {
+            category: "promoter_cost".to_string(),
+            name: cost.name.clone(),
+            amount: amount.to_f64(),
+        });
+    }
+
+    // Net deals are settled on NBOR less promoter costs; gross deals on the gross itself
+    let payout = deal.payout(
+        &DealInputs {
+            tickets_sold,
+            capacity,
+            gross_box_office: Some(gross.to_f64()),
+            promoter_expenses: total_deductions.checked_add(&total_costs)?.to_f64(),
+        },
+        currency,
+        mode,
+    )?;
+
+    let guarantee = match deal.structure {
+        DealStructure::Flat { guarantee }
+        | DealStructure::Versus { guarantee, .. }
+        | DealStructure::GuaranteePlus { guarantee, .. } => money(guarantee),
+        DealStructure::Door { .. } => Money::zero(currency),
+    };
+    let artist_share = money(payout.payout);
+    let overage = artist_share.checked_sub(&guarantee)?;
+    let overage = if overage.is_negative() {
+        Money::zero(currency)
+    } else {
+        overage
+    };
+    let deposits = money(input.deposits_paid);
+
+    Ok(SettlementSheet {
+        currency,
+        tiers,
+        tickets_sold,
+        comps,
+        gross_box_office: gross.to_f64(),
+        deductions,
+        total_deductions: total_deductions.to_f64(),
+        net_box_office_receipts: nbor.to_f64(),
+        promoter_costs,
+        total_promoter_costs: total_costs.to_f64(),
+        net_after_costs: nbor.checked_sub(&total_costs)?.to_f64(),
+        deal: payout,
+        guarantee: guarantee.to_f64(),
+        artist_share: artist_share.to_f64(),
+        overage: overage.to_f64(),
+        deposits_paid: deposits.to_f64(),
+        amount_due: artist_share.checked_sub(&deposits)?.to_f64(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deal::DealBasis;
+
+    fn input(tiers: Vec<TierSales>) -> SettlementInput {
+        SettlementInput {
+            show_index: None,
+            currency: None,
+            capacity: None,
+            tiers,
+            ticketing_fees: vec![Deduction {
+                name: "Ticketing".to_string(),
+                rule: DeductionRule::PerTicket { amount: 2.0 },
+            }],
+            facility_fees: Vec::new(),
+            taxes: vec![Deduction {
+                name: "VAT".to_string(),
+                rule: DeductionRule::IncludedTax { percentage: 10.0 },
+            }],
+            promoter_costs: vec![PromoterCost {
+                name: "Production".to_string(),
+                amount: 1000.0,
+            }],
+            deal: None,
+            deposits_paid: 500.0,
+        }
+    }
+
+    fn tier(name: &str, price: f64, sold: u32) -> TierSales {
+        TierSales {
+            name: name.to_string(),
+            price,
+            sold,
+            comps: 5,
+        }
+    }
+
+    fn door(basis: DealBasis) -> DealTerms {
+        DealTerms {
+            structure: DealStructure::Door {
+                percentage: 50.0,
+                basis,
+            },
+            bonuses: Vec::new(),
+            cap: None,
+        }
+    }
+
+    fn settle_with(deal: &DealTerms) -> SettlementSheet {
+        let input = input(vec![tier("GA", 25.0, 400), tier("VIP", 110.0, 50)]);
+        settle(
+            &input,
+            deal,
+            Currency::default(),
+            500,
+            RoundingMode::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn receipts_are_gross_less_fees_and_taxes() {
+        let sheet = settle_with(&door(DealBasis::NetAfterExpenses));
+        assert_eq!(sheet.gross_box_office, 15500.0);
+        assert_eq!(sheet.tickets_sold, 450);
+        assert_eq!(sheet.comps, 10);
+        // 900 in ticketing fees, 10/110 of gross in VAT
+        assert_eq!(sheet.total_deductions, 2309.09);
+        assert_eq!(sheet.net_box_office_receipts, 13190.91);
+        assert_eq!(sheet.net_after_costs, 12190.91);
+    }
+
+    #[test]
+    fn deal_basis_picks_gross_or_net() {
+        let net = settle_with(&door(DealBasis::NetAfterExpenses));
+        assert_eq!(net.deal.basis_amount, 12190.91);
+        assert_eq!(net.artist_share, 6095.46);
+        assert_eq!(net.amount_due, 5595.46);
+
+        let gross = settle_with(&door(DealBasis::GrossBoxOffice));
+        assert_eq!(gross.deal.basis_amount, 15500.0);
+        assert_eq!(gross.artist_share, 7750.0);
+    }
+
+    #[test]
+    fn ticket_counts_do_not_overflow() {
+        let input = input(vec![tier("A", 1.0, u32::MAX), tier("B", 1.0, 1)]);
+        let deal = door(DealBasis::GrossBoxOffice);
+        assert!(settle(
+            &input,
+            &deal,
+            Currency::default(),
+            0,
+            RoundingMode::default()
+        )
+        .is_err());
+    }
+}
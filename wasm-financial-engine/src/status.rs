@@ -0,0 +1,239 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::schedule::EntityKind;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeType {
+    #[serde(alias = "postpone")]
+    Delay,
+    #[serde(alias = "completed")]
+    Complete,
+    Reschedule,
+    #[serde(alias = "canceled", alias = "cancelled")]
+    Cancel,
+}
+
+impl ChangeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeType::Delay => "delay",
+            ChangeType::Complete => "complete",
+            ChangeType::Reschedule => "reschedule",
+            ChangeType::Cancel => "cancel",
+        }
+    }
+
+    /// Delays and reschedules move the entity to a new date
+    pub fn moves_date(&self) -> bool {
+        matches!(self, ChangeType::Delay | ChangeType::Reschedule)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    Draft,
+    #[serde(alias = "todo", alias = "not_started")]
+    Pending,
+    Scheduled,
+    #[serde(alias = "in-progress", alias = "active")]
+    InProgress,
+    Blocked,
+    #[serde(alias = "done", alias = "complete")]
+    Completed,
+    #[serde(alias = "canceled")]
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskPriority {
+    #[serde(alias = "urgent")]
+    Critical,
+    High,
+    #[serde(alias = "normal")]
+    Medium,
+    Low,
+}
+
+impl TaskPriority {
+    /// Scheduling rank, most urgent first
+    pub fn rank(&self) -> u8 {
+        match self {
+            TaskPriority::Critical => 0,
+            TaskPriority::High => 1,
+            TaskPriority::Medium => 2,
+            TaskPriority::Low => 3,
+        }
+    }
+}
+
+/// What is being released; whether it is out yet is its `ReleaseStatus`
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseType {
+    Single,
+    Ep,
+    Album,
+    Compilation,
+    Live,
+    Remix,
+    Video,
+    #[serde(alias = "merch")]
+    Merchandise,
+    Promotional,
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseStatus {
+    #[default]
+    #[serde(alias = "draft")]
+    Planned,
+    Scheduled,
+    Released,
+    #[serde(alias = "delayed")]
+    Postponed,
+    #[serde(alias = "canceled")]
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ShowStatus {
+    Draft,
+    Offer,
+    #[serde(alias = "tentative")]
+    Pending,
+    #[serde(alias = "upcoming")]
+    Confirmed,
+    Scheduled,
+    /// Currently on sale or in progress
+    Active,
+    Completed,
+    Postponed,
+    #[serde(alias = "canceled")]
+    Cancelled,
+    Archived,
+}
+
+/// The status a change sets, typed by the kind of entity it targets
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(untagged)]
+pub enum EntityStatus {
+    Task(TaskStatus),
+    Release(ReleaseStatus),
+    Show(ShowStatus),
+}
+
+impl EntityStatus {
+    /// Parse `value` as one of `kind`'s statuses
+    pub fn parse(kind: EntityKind, value: &str) -> Result<EntityStatus, String> {
+        let status = match kind {
+            EntityKind::Task => parse(value).map(EntityStatus::Task),
+            EntityKind::Release => parse(value).map(EntityStatus::Release),
+            EntityKind::Show => parse(value).map(EntityStatus::Show),
+        };
+        status.map_err(|e| format!("invalid {} status: {}", kind.as_str(), e))
+    }
+
+    /// The kind of entity this status belongs to
+    pub fn kind(&self) -> EntityKind {
+        match self {
+            EntityStatus::Task(_) => EntityKind::Task,
+            EntityStatus::Release(_) => EntityKind::Release,
+            EntityStatus::Show(_) => EntityKind::Show,
+        }
+    }
+}
+
+/// Older payloads marked a released release with `release_type: "released"` instead of its
+/// status. Rewrite those in place to type `other` with status `released`.
+pub fn migrate_legacy_releases(data: &mut serde_json::Value) {
+    let Some(releases) = data.get_mut("releases").and_then(|r| r.as_array_mut()) else {
+        return;
+    };
+    for release in releases.iter_mut().filter_map(|r| r.as_object_mut()) {
+        if release.get("release_type").and_then(|t| t.as_str()) == Some("released") {
+            release.insert("release_type".to_string(), "other".into());
+            release.insert("status".to_string(), "released".into());
+        }
+    }
+}
+
+/// Parse one status value with the same names and aliases as JSON input
+pub fn parse<T: DeserializeOwned>(value: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_string())).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn app_statuses_parse() {
+        for value in ["draft", "scheduled", "active", "completed", "cancelled"] {
+            assert!(parse::<TaskStatus>(value).is_ok(), "task {}", value);
+            assert!(parse::<ShowStatus>(value).is_ok(), "show {}", value);
+        }
+        assert_eq!(parse::<TaskStatus>("active"), Ok(TaskStatus::InProgress));
+        assert_eq!(parse::<TaskStatus>("canceled"), Ok(TaskStatus::Cancelled));
+        assert_eq!(parse::<ReleaseStatus>("draft"), Ok(ReleaseStatus::Planned));
+        assert_eq!(parse::<ChangeType>("postpone"), Ok(ChangeType::Delay));
+        assert!(parse::<ShowStatus>("sold_out").is_err());
+    }
+
+    #[test]
+    fn change_statuses_follow_the_entity_kind() {
+        assert_eq!(
+            EntityStatus::parse(EntityKind::Task, "done"),
+            Ok(EntityStatus::Task(TaskStatus::Completed))
+        );
+        assert_eq!(
+            EntityStatus::parse(EntityKind::Release, "draft"),
+            Ok(EntityStatus::Release(ReleaseStatus::Planned))
+        );
+        assert!(EntityStatus::parse(EntityKind::Release, "blocked").is_err());
+        assert_eq!(
+            serde_json::to_value(EntityStatus::Show(ShowStatus::Active)).unwrap(),
+            json!("active")
+        );
+    }
+
+    #[test]
+    fn app_release_types_parse() {
+        for value in [
+            "single",
+            "album",
+            "ep",
+            "video",
+            "merchandise",
+            "promotional",
+        ] {
+            assert!(
+                parse::<ReleaseType>(value).is_ok(),
+                "release type {}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn legacy_released_type_becomes_status() {
+        let mut data = json!({
+            "releases": [
+                { "id": "old", "release_type": "released" },
+                { "id": "new", "release_type": "single", "status": "scheduled" }
+            ]
+        });
+        migrate_legacy_releases(&mut data);
+        assert_eq!(data["releases"][0]["release_type"], "other");
+        assert_eq!(data["releases"][0]["status"], "released");
+        assert_eq!(data["releases"][1]["release_type"], "single");
+        assert_eq!(data["releases"][1]["status"], "scheduled");
+    }
+}
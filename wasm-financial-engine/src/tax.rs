@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::money::{self, Currency, Money, RoundingMode};
+
+/// Whether withholding tax is levied on the gross fee or on the fee net of deductible costs
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WhtApplicationPoint {
+    #[default]
+    Gross,
+    Net,
+}
+
+/// Withholding tax and VAT rules for performances in one country. Rates are 0-100.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CountryTaxRule {
+    pub country: String,
+    #[serde(default)]
+    pub wht_rate: f64,
+    #[serde(default)]
+    pub wht_application: WhtApplicationPoint,
+    /// Reduced rate for artists covered by a tax treaty (certificate of residence filed)
+    #[serde(default)]
+    pub treaty_rate: Option<f64>,
+    /// Fees below this amount (in the base currency) are exempt from withholding
+    #[serde(default, deserialize_with = "money::deserialize_opt_amount")]
+    pub wht_threshold: Option<f64>,
+    #[serde(default)]
+    pub vat_rate: f64,
+    /// VAT is accounted for by the promoter, so none is invoiced
+    #[serde(default)]
+    pub reverse_charge: bool,
+}
+
+impl CountryTaxRule {
+    fn validate(&self) -> Result<(), String> {
+        let rates = [Some(self.wht_rate), self.treaty_rate, Some(self.vat_rate)];
+        if rates.iter().flatten().any(|r| !(0.0..=100.0).contains(r)) {
+            return Err(format!(
+                "Tax rates for {} must be between 0 and 100",
+                self.country
+            ));
+        }
+        if self.wht_threshold.is_some_and(|t| t < 0.0) {
+            return Err(format!(
+                "Withholding threshold for {} cannot be negative",
+                self.country
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Tax rules keyed by upper-case country code
+#[derive(Default)]
+pub struct TaxRules {
+    rules: HashMap<String, CountryTaxRule>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ShowTax {
+    pub country: Option<String>,
+    /// False when the show has no country or no rule is loaded for it
+    pub rule_applied: bool,
+    pub gross: f64,
+    pub wht_rate: f64,
+    pub wht_application: WhtApplicationPoint,
+    pub wht_base: f64,
+    pub wht: f64,
+    pub vat_rate: f64,
+    pub reverse_charge: bool,
+    pub vat: f64,
+    pub invoice_total: f64,
+    /// Gross minus withholding tax and expenses; VAT does not reduce net
+    pub net: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct TaxSummary {
+    pub total_gross: f64,
+    pub total_wht: f64,
+    pub total_vat: f64,
+    pub total_invoiced: f64,
+    pub total_net: f64,
+    pub shows: Vec<ShowTax>,
+}
+
+impl TaxRules {
+    pub fn from_rules(rules: Vec<CountryTaxRule>) -> Result<TaxRules, String> {
+        let mut by_country = HashMap::with_capacity(rules.len());
+        for rule in rules {
+            rule.validate()?;
+            by_country.insert(rule.country.trim().to_ascii_uppercase(), rule);
+        }
+        Ok(TaxRules { rules: by_country })
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn get(&self, country: &str) -> Option<&CountryTaxRule> {
+        self.rules.get(&country.trim().to_ascii_uppercase())
+    }
+
+    /// Withholding and VAT for one show's fee (`gross`) and deductible `expenses`
+    pub fn show_tax(
+        &self,
+        country: Option<&str>,
+        treaty_relief: bool,
+        gross: Money,
+        expenses: Money,
+        mode: RoundingMode,
+    ) -> Result<ShowTax, String> {
+        let currency: Currency = gross.currency();
+        let zero = Money::zero(currency);
+        let rule = country.and_then(|c| self.get(c));
+
+        let (wht_rate, wht_application, wht_base, wht) = match rule {
+            Some(rule) => {
+                let rate = match (treaty_relief, rule.treaty_rate) {
+                    (true, Some(treaty)) => treaty,
+                    _ => rule.wht_rate,
+                };
+                let base = match rule.wht_application {
+                    WhtApplicationPoint::Gross => gross,
+                    WhtApplicationPoint::Net => gross.checked_sub(&expenses)?,
+                };
+                let exempt = rule.wht_threshold.is_some_and(|t| {
+                    gross.minor_units() < Money::from_f64(t, currency, mode).minor_units()
+                });
+                let wht = if exempt || base.is_negative() {
+                    zero
+                } else {
+                    base.multiply(rate / 100.0, mode)?
+                };
+                (
+                    if exempt { 0.0 } else { rate },
+                    rule.wht_application,
+                    base,
+                    wht,
+                )
+            }
+            None => (0.0, WhtApplicationPoint::Gross, gross, zero),
+        };
+
+        let (vat_rate, reverse_charge) = match rule {
+            Some(rule) => (rule.vat_rate, rule.reverse_charge),
+            None => (0.0, false),
+        };
+        let vat = if reverse_charge {
+            zero
+        } else {
+            gross.multiply(vat_rate / 100.0, mode)?
+        };
+
+        Ok(ShowTax {
+            country: country.map(|c| c.to_string()),
+            rule_applied: rule.is_some(),
+            gross: gross.to_f64(),
+            wht_rate,
+            wht_application,
+            wht_base: wht_base.to_f64(),
+            wht: wht.to_f64(),
+            vat_rate,
+            reverse_charge,
+            vat: vat.to_f64(),
+            invoice_total: gross.checked_add(&vat)?.to_f64(),
+            net: gross.checked_sub(&wht)?.checked_sub(&expenses)?.to_f64(),
+        })
+    }
+}
+
+impl TaxSummary {
+    /// Total the per-show figures in minor units so the summary matches the rows exactly
+    pub fn from_shows(shows: Vec<ShowTax>, currency: Currency, mode: RoundingMode) -> TaxSummary {
+        let total =
+            |f: fn(&ShowTax) -> f64| Money::sum_f64(shows.iter().map(f), currency, mode).to_f64();
+        TaxSummary {
+            total_gross: total(|s| s.gross),
+            total_wht: total(|s| s.wht),
+            total_vat: total(|s| s.vat),
+            total_invoiced: total(|s| s.invoice_total),
+            total_net: total(|s| s.net),
+            shows,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rules() -> TaxRules {
+        TaxRules::from_rules(
+            serde_json::from_value(json!([
+                { "country": "de", "wht_rate": 15.825, "vat_rate": 19, "treaty_rate": 5 },
+                { "country": "NL", "wht_rate": 20, "wht_application": "net", "vat_rate": 21 },
+                { "country": "UK", "wht_rate": 20, "wht_threshold": "1000.00", "reverse_charge": true, "vat_rate": 20 }
+            ]))
+            .unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn eur(amount: f64) -> Money {
+        Money::from_f64(amount, Currency::default(), RoundingMode::default())
+    }
+
+    fn tax(country: Option<&str>, treaty: bool, gross: f64, expenses: f64) -> ShowTax {
+        rules()
+            .show_tax(
+                country,
+                treaty,
+                eur(gross),
+                eur(expenses),
+                RoundingMode::default(),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn withholding_on_gross_with_treaty_relief() {
+        let de = tax(Some(" De "), false, 10000.0, 3000.0);
+        assert!(de.rule_applied);
+        assert_eq!(
+            (de.wht, de.vat, de.invoice_total),
+            (1582.5, 1900.0, 11900.0)
+        );
+        assert_eq!(de.net, 5417.5);
+        let treaty = tax(Some("DE"), true, 10000.0, 3000.0);
+        assert_eq!((treaty.wht_rate, treaty.wht), (5.0, 500.0));
+    }
+
+    #[test]
+    fn withholding_on_net_never_goes_negative() {
+        let nl = tax(Some("NL"), false, 10000.0, 4000.0);
+        assert_eq!((nl.wht_base, nl.wht), (6000.0, 1200.0));
+        let loss = tax(Some("NL"), false, 1000.0, 4000.0);
+        assert_eq!(loss.wht, 0.0);
+    }
+
+    #[test]
+    fn small_fees_and_reverse_charge_are_exempt() {
+        let small = tax(Some("UK"), false, 999.99, 0.0);
+        assert_eq!((small.wht_rate, small.wht, small.vat), (0.0, 0.0, 0.0));
+        let large = tax(Some("UK"), false, 5000.0, 0.0);
+        assert_eq!(
+            (large.wht, large.vat, large.invoice_total),
+            (1000.0, 0.0, 5000.0)
+        );
+    }
+
+    #[test]
+    fn shows_without_a_rule_are_untaxed() {
+        for country in [None, Some("FR")] {
+            let show = tax(country, false, 2000.0, 500.0);
+            assert!(!show.rule_applied);
+            assert_eq!((show.wht, show.vat, show.net), (0.0, 0.0, 1500.0));
+        }
+    }
+
+    #[test]
+    fn summary_totals_the_rows() {
+        let shows = vec![
+            tax(Some("DE"), false, 10000.0, 3000.0),
+            tax(Some("NL"), false, 10000.0, 4000.0),
+        ];
+        let summary = TaxSummary::from_shows(shows, Currency::default(), RoundingMode::default());
+        assert_eq!((summary.total_gross, summary.total_wht), (20000.0, 2782.5));
+        assert_eq!(summary.total_vat, 4000.0);
+    }
+
+    #[test]
+    fn rates_outside_0_to_100_are_rejected() {
+        let rules = serde_json::from_value(json!([{ "country": "DE", "wht_rate": 101 }])).unwrap();
+        assert!(TaxRules::from_rules(rules).is_err());
+    }
+
+    #[test]
+    fn negative_thresholds_are_rejected() {
+        let rules = serde_json::from_value(json!([
+            { "country": "FR", "wht_rate": 15, "wht_threshold": "-0.01" }
+        ]))
+        .unwrap();
+        assert!(TaxRules::from_rules(rules).is_err());
+    }
+}
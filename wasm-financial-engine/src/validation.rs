@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::schedule::EntityKind;
+use crate::TimelineData;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    /// Dependencies loop back on themselves
+    Circular,
+    /// Dependency on an ID that no task, release or show has
+    BrokenDependency,
+    /// The same ID is used by more than one entity
+    DuplicateId,
+    SelfDependency,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Diagnostic {
+    #[serde(rename = "type")]
+    pub kind: DiagnosticKind,
+    pub severity: Severity,
+    pub entity_id: String,
+    pub entity_type: EntityKind,
+    /// The missing ID of a broken dependency
+    pub related_id: Option<String>,
+    /// For cycles, the IDs in execution order, ending with the first ID repeated
+    pub path: Vec<String>,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ValidationReport {
+    /// False when any diagnostic is an error
+    pub valid: bool,
+    pub errors: usize,
+    pub warnings: usize,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+struct Entity<'a> {
+    id: &'a str,
+    kind: EntityKind,
+    dependencies: &'a [String],
+}
+
+fn entities(data: &TimelineData) -> Vec<Entity<'_>> {
+    let tasks = data.tasks.iter().map(|t| Entity {
+        id: &t.id,
+        kind: EntityKind::Task,
+        dependencies: &t.dependencies,
+    });
+    let releases = data.releases.iter().map(|r| Entity {
+        id: &r.id,
+        kind: EntityKind::Release,
+        dependencies: &r.dependencies,
+    });
+    let shows = data.shows.iter().map(|s| Entity {
+        id: &s.id,
+        kind: EntityKind::Show,
+        dependencies: &s.dependencies,
+    });
+    tasks.chain(releases).chain(shows).collect()
+}
+
+/// `Task`, `Release` or `Show`, for the start of a message
+fn label(kind: EntityKind) -> String {
+    let name = kind.as_str();
+    name[..1].to_ascii_uppercase() + &name[1..]
+}
+
+/// Check the dependency graph for duplicate IDs, self and dangling dependencies, and cycles
+pub fn validate(data: &TimelineData) -> ValidationReport {
+    let entities = entities(data);
+    let mut diagnostics = Vec::new();
+
+    let mut index: HashMap<&str, usize> = HashMap::with_capacity(entities.len());
+    for (i, entity) in entities.iter().enumerate() {
+        match index.get(entity.id) {
+            Some(&first) => diagnostics.push(Diagnostic {
+                kind: DiagnosticKind::DuplicateId,
+                severity: Severity::Error,
+                entity_id: entity.id.to_string(),
+                entity_type: entity.kind,
+                related_id: None,
+                path: Vec::new(),
+                message: if entities[first].kind == entity.kind {
+                    format!(
+                        "ID '{}' is used by more than one {}",
+                        entity.id,
+                        entity.kind.as_str()
+                    )
+                } else {
+                    format!(
+                        "ID '{}' is used by both a {} and a {}",
+                        entity.id,
+                        entities[first].kind.as_str(),
+                        entity.kind.as_str()
+                    )
+                },
+            }),
+            None => {
+                index.insert(entity.id, i);
+            }
+        }
+    }
+
+    let mut successors = vec![Vec::new(); entities.len()];
+    for (i, entity) in entities.iter().enumerate() {
+        for dependency in entity.dependencies {
+            if dependency == entity.id {
+                diagnostics.push(Diagnostic {
+                    kind: DiagnosticKind::SelfDependency,
+                    severity: Severity::Warning,
+                    entity_id: entity.id.to_string(),
+                    entity_type: entity.kind,
+                    related_id: None,
+                    path: Vec::new(),
+                    message: format!("{} '{}' depends on itself", label(entity.kind), entity.id),
+                });
+                continue;
+            }
+            match index.get(dependency.as_str()) {
+                Some(&d) => successors[d].push(i),
+                None => diagnostics.push(Diagnostic {
+                    kind: DiagnosticKind::BrokenDependency,
+                    severity: Severity::Error,
+                    entity_id: entity.id.to_string(),
+                    entity_type: entity.kind,
+                    related_id: Some(dependency.clone()),
+                    path: Vec::new(),
+                    message: format!(
+                        "{} '{}' depends on unknown ID '{}'",
+                        label(entity.kind),
+                        entity.id,
+                        dependency
+                    ),
+                }),
+            }
+        }
+    }
+
+    for cycle in find_cycles(&successors) {
+        let first = &entities[cycle[0]];
+        let path: Vec<String> = cycle.iter().map(|&i| entities[i].id.to_string()).collect();
+        diagnostics.push(Diagnostic {
+            kind: DiagnosticKind::Circular,
+            severity: Severity::Error,
+            entity_id: first.id.to_string(),
+            entity_type: first.kind,
+            related_id: None,
+            message: format!("Circular dependency: {}", path.join(" → ")),
+            path,
+        });
+    }
+
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    ValidationReport {
+        valid: errors == 0,
+        errors,
+        warnings: diagnostics.len() - errors,
+        diagnostics,
+    }
+}
+
+/// Iterative depth-first search reporting one path per back edge, closed by repeating
+/// its first node
+fn find_cycles(successors: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    const UNVISITED: u8 = 0;
+    const ON_STACK: u8 = 1;
+    const DONE: u8 = 2;
+
+    let mut state = vec![UNVISITED; successors.len()];
+    let mut cycles = Vec::new();
+    for root in 0..successors.len() {
+        if state[root] != UNVISITED {
+            continue;
+        }
+        state[root] = ON_STACK;
+        // (node, index of the next successor to visit)
+        let mut stack = vec![(root, 0)];
+        while let Some(&(node, next)) = stack.last() {
+            match successors[node].get(next) {
+                Some(&s) => {
+                    if let Some(top) = stack.last_mut() {
+                        top.1 += 1;
+                    }
+                    match state[s] {
+                        UNVISITED => {
+                            state[s] = ON_STACK;
+                            stack.push((s, 0));
+                        }
+                        ON_STACK => {
+                            let from = stack.iter().position(|&(n, _)| n == s).unwrap_or(0);
+                            let mut cycle: Vec<usize> =
+                                stack[from..].iter().map(|&(n, _)| n).collect();
+                            cycle.push(s);
+                            cycles.push(cycle);
+                        }
+                        _ => {}
+                    }
+                }
+                None => {
+                    state[node] = DONE;
+                    stack.pop();
+                }
+            }
+        }
+    }
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::tests::{release, show, task};
+
+    fn kinds(report: &ValidationReport) -> Vec<DiagnosticKind> {
+        report.diagnostics.iter().map(|d| d.kind).collect()
+    }
+
+    #[test]
+    fn clean_graph_is_valid() {
+        let data = TimelineData {
+            tasks: vec![
+                task("mix", "2025-01-08", 8.0, &[]),
+                task("master", "2025-01-09", 8.0, &["mix"]),
+            ],
+            releases: vec![release("single", "2025-01-10", &["master"])],
+            shows: vec![show("launch", "2025-01-11", &["single"])],
+        };
+        let report = validate(&data);
+        assert!(report.valid);
+        assert!(report.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn cycles_are_reported_in_execution_order() {
+        let data = TimelineData {
+            tasks: vec![
+                task("a", "2025-01-08", 8.0, &["c"]),
+                task("b", "2025-01-08", 8.0, &["a"]),
+                task("c", "2025-01-08", 8.0, &["b"]),
+            ],
+            releases: Vec::new(),
+            shows: Vec::new(),
+        };
+        let report = validate(&data);
+        assert!(!report.valid);
+        assert_eq!(kinds(&report), [DiagnosticKind::Circular]);
+        assert_eq!(report.diagnostics[0].path, ["a", "b", "c", "a"]);
+        assert_eq!(
+            report.diagnostics[0].message,
+            "Circular dependency: a → b → c → a"
+        );
+    }
+
+    #[test]
+    fn broken_duplicate_and_self_dependencies() {
+        let data = TimelineData {
+            tasks: vec![
+                task("mix", "2025-01-08", 8.0, &["mix", "ghost"]),
+                task("mix", "2025-01-09", 8.0, &[]),
+            ],
+            releases: vec![release("single", "2025-01-10", &[])],
+            shows: vec![show("single", "2025-01-11", &[])],
+        };
+        let report = validate(&data);
+        assert_eq!(
+            kinds(&report),
+            [
+                DiagnosticKind::DuplicateId,
+                DiagnosticKind::DuplicateId,
+                DiagnosticKind::SelfDependency,
+                DiagnosticKind::BrokenDependency,
+            ]
+        );
+        assert_eq!((report.errors, report.warnings), (3, 1));
+        assert_eq!(
+            report.diagnostics[1].message,
+            "ID 'single' is used by both a release and a show"
+        );
+        assert_eq!(report.diagnostics[3].related_id.as_deref(), Some("ghost"));
+    }
+}